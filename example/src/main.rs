@@ -1,10 +1,3 @@
-#[derive(Debug, PartialEq, Eq, rhosql::FromRow)]
-struct User {
-    id: i32,
-    name: String,
-    item: String,
-}
-
 fn main() {
     env_logger::init();
     query_api().unwrap();
@@ -18,7 +11,7 @@ fn query_api() -> rhosql::Result<()> {
     // derive macro
     #[derive(Debug, rhosql::FromRow)]
     struct Post {
-        id: i32,
+        id: i64,
         name: String,
     }
 
@@ -29,26 +22,26 @@ fn query_api() -> rhosql::Result<()> {
 
     let id = rhosql::query("insert into post(name) values(?1)", &mut db)
         .bind("Control")
-        .execute()?;
+        .execute()?.last_insert_rowid;
 
     // using custom struct
     let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<Post>()?;
 
-    assert_eq!(posts[0].id as i64, id);
+    assert_eq!(posts[0].id, id);
     assert_eq!(posts[0].name, "Control");
 
     // using tuple
     // also, prepared statement is cached
-    let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i32, String)>()?;
+    let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i64, String)>()?;
 
-    assert_eq!(posts[0].0 as i64, id);
+    assert_eq!(posts[0].0, id);
     assert_eq!(posts[0].1, "Control");
 
     // iterator based
     let mut posts = rhosql::query("select rowid,* from post", &mut db).fetch()?;
 
     while let Some(post) = posts.next_row::<Post>()? {
-        assert_eq!(post.id as i64, id);
+        assert_eq!(post.id, id);
         assert_eq!(post.name, "Control");
     }
 
@@ -85,7 +78,7 @@ fn multithread_lock() -> rhosql::Result<()> {
     t1.join().unwrap()?;
     t2.join().unwrap()?;
 
-    let posts = rhosql::query("select rowid,name from post", &dbd).fetch_all::<(i32, String)>()?;
+    let posts = rhosql::query("select rowid,name from post", &dbd).fetch_all::<(i64, String)>()?;
 
     assert!(posts.iter().find(|e|matches!(e.1.as_str(),"John")).is_some());
     assert!(posts.iter().find(|e|matches!(e.1.as_str(),"Diesel")).is_some());
@@ -0,0 +1,80 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, *};
+
+use crate::from_row::{Container, FieldAttr};
+
+pub fn bind(input: DeriveInput) -> Result<TokenStream> {
+    let DeriveInput { attrs, vis: _, ident, generics, data } = input;
+    let data = match data {
+        Data::Struct(data) => data,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(Error::new_spanned(enum_token, "only struct are currently supported"));
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(Error::new_spanned(union_token, "only struct are currently supported"));
+        }
+    };
+
+    let container = Container::from_attrs(&attrs)?;
+    if let (Some(path), Fields::Unnamed(_)) = (&container.by_name, &data.fields) {
+        return Err(Error::new_spanned(path, "`by_name` requires named fields"));
+    }
+
+    let lt = Lifetime::new("'__r", proc_macro2::Span::call_site());
+    let mut impl_generics = generics.clone();
+    impl_generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+    let mut where_clause = impl_generics.where_clause.clone().unwrap_or_else(||parse_quote!(where));
+
+    let mut body = quote! {};
+
+    for (i, field) in data.fields.iter().enumerate() {
+        let attr = FieldAttr::from_attrs(&field.attrs)?;
+        if attr.skip.is_some() {
+            continue;
+        }
+        if container.by_name.is_none()
+            && let Some(span) = attr.by_name_only()
+        {
+            return Err(Error::new_spanned(span, "`by_name` required for this attribute"));
+        }
+
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(id) => Member::Named(id.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        };
+
+        if attr.flatten.is_some() {
+            where_clause.predicates.push(parse_quote!(&#lt #ty: ::rhosql::query::Bind<#lt>));
+            match &attr.prefix {
+                Some(prefix) => body.extend(quote! { let query = query.bind_all_prefixed(#prefix, &self.#member); }),
+                None => body.extend(quote! { let query = ::rhosql::query::Bind::bind_all(&self.#member, query); }),
+            }
+            continue;
+        }
+
+        where_clause.predicates.push(parse_quote!(&#lt #ty: ::rhosql::Encode<#lt>));
+
+        match (&container.by_name, &field.ident) {
+            (Some(_), Some(id)) => {
+                let name = attr.rename.as_ref().map_or_else(||id.unraw().to_string(), LitStr::value);
+                let name = format!(":{name}");
+                body.extend(quote! { let query = query.bind_named(#name, &self.#member); });
+            }
+            _ => body.extend(quote! { let query = query.bind(&self.#member); }),
+        }
+    }
+
+    let (g1, _, _) = impl_generics.split_for_impl();
+    let (_, g2, _) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #g1 ::rhosql::query::Bind<#lt> for &#lt #ident #g2 #where_clause {
+            fn bind_all<S, E>(self, query: ::rhosql::query::Query<#lt, S, E>) -> ::rhosql::query::Query<#lt, S, E> {
+                #body
+                query
+            }
+        }
+    })
+}
@@ -1,51 +1,315 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 use syn::{
+    ext::IdentExt,
+    punctuated::Punctuated,
     token::{Brace, Paren},
     *,
 };
 
-macro_rules! error {
-    ($($tt:tt)*) => {
-        return Err(syn::Error::new(proc_macro2::Span::call_site(), format!($($tt)*)))
+/// container attributes, `#[sql(...)]`
+#[derive(Default)]
+pub(crate) struct Container {
+    /// match field to column by name instead of position
+    pub(crate) by_name: Option<Path>,
+    /// column name which select the enum variant
+    pub(crate) tag: Option<LitStr>,
+    /// variant name case conversion for tag value
+    pub(crate) rename_all: Option<LitStr>,
+}
+
+impl Container {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut me = Self::default();
+        for attr in attrs.iter().filter(|e|e.path().is_ident("sql")) {
+            attr.parse_nested_meta(|meta|{
+                if meta.path.is_ident("by_name") {
+                    me.by_name = Some(meta.path);
+                    Ok(())
+                } else if meta.path.is_ident("tag") {
+                    me.tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    me.rename_all = Some(crate::value::parse_rename_all(&meta)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown attribute"))
+                }
+            })?;
+        }
+        Ok(me)
+    }
+}
+
+/// field attributes, `#[sql(...)]`
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    /// column name, instead of field name
+    pub(crate) rename: Option<LitStr>,
+    /// use `Default` when column not found or `NULL`
+    pub(crate) default: Option<Path>,
+    /// use `Default` without decoding
+    pub(crate) skip: Option<Path>,
+    /// decode field as `FromRow` from the same row
+    pub(crate) flatten: Option<Path>,
+    /// column name prefix for flattened field
+    pub(crate) prefix: Option<LitStr>,
+    /// decode as given type, then convert via `TryFrom`
+    pub(crate) try_from: Option<Type>,
+}
+
+impl FieldAttr {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut me = Self::default();
+        for attr in attrs.iter().filter(|e|e.path().is_ident("sql")) {
+            attr.parse_nested_meta(|meta|{
+                if meta.path.is_ident("rename") {
+                    me.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    me.default = Some(meta.path);
+                } else if meta.path.is_ident("skip") {
+                    me.skip = Some(meta.path);
+                } else if meta.path.is_ident("flatten") {
+                    me.flatten = Some(meta.path);
+                } else if meta.path.is_ident("prefix") {
+                    me.prefix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("try_from") {
+                    let ty: LitStr = meta.value()?.parse()?;
+                    me.try_from = Some(ty.parse()?);
+                } else {
+                    return Err(meta.error("unknown attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        me.validate()?;
+        Ok(me)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(skip) = &self.skip
+            && (self.rename.is_some() || self.default.is_some() || self.flatten.is_some()
+                || self.prefix.is_some() || self.try_from.is_some())
+        {
+            return Err(Error::new_spanned(skip, "`skip` cannot be combined with other attribute"));
+        }
+        if let Some(flatten) = &self.flatten
+            && (self.rename.is_some() || self.default.is_some() || self.try_from.is_some())
+        {
+            return Err(Error::new_spanned(flatten, "`flatten` can only be combined with `prefix`"));
+        }
+        if let Some(prefix) = &self.prefix
+            && self.flatten.is_none()
+        {
+            return Err(Error::new_spanned(prefix, "`prefix` requires `flatten`"));
+        }
+        Ok(())
+    }
+
+    /// attributes which requires `by_name`
+    pub(crate) fn by_name_only(&self) -> Option<TokenStream> {
+        self.rename.as_ref().map(ToTokens::to_token_stream)
+            .or_else(||self.flatten.as_ref().map(ToTokens::to_token_stream))
+    }
+}
+
+/// where the field is decoded from
+enum Source {
+    Name(String),
+    Index(Index),
+}
+
+/// generate expression that decode a single field
+fn field_expr(attr: &FieldAttr, field: &str, source: Source) -> TokenStream {
+    if attr.skip.is_some() {
+        return quote! { ::core::default::Default::default() };
+    }
+
+    if attr.flatten.is_some() {
+        let row = match &attr.prefix {
+            Some(prefix) => quote! { row.with_prefix(#prefix) },
+            None => quote! { ::core::clone::Clone::clone(&row) },
+        };
+        return quote! { ::rhosql::FromRow::from_row(#row)? };
+    }
+
+    let ty = match &attr.try_from {
+        Some(ty) => ty.to_token_stream(),
+        None => quote! { _ },
+    };
+
+    let value = match (source, attr.default.is_some()) {
+        (Source::Name(name), false) => quote! { row.try_get::<#ty>(#name)? },
+        (Source::Name(name), true) => quote! { row.try_get_optional::<#ty>(#name)?.unwrap_or_default() },
+        (Source::Index(i), false) => quote! { row.try_decode::<#ty>(#i)? },
+        (Source::Index(i), true) => quote! { row.try_decode_optional::<#ty>(#i)?.unwrap_or_default() },
     };
+
+    match &attr.try_from {
+        Some(_) => quote! {
+            ::core::convert::TryFrom::try_from(#value).map_err(|err| {
+                ::rhosql::sqlite::error::DecodeError::Custom(
+                    ::std::format!("failed to convert field `{}`: {}", #field, err)
+                )
+            })?
+        },
+        None => value,
+    }
+}
+
+/// generate struct body of named fields
+fn named_fields(named: &Punctuated<Field, Token![,]>, by_name: bool) -> Result<TokenStream> {
+    let mut idx = 0;
+    let mut body = quote! {};
+    for field in named {
+        let attr = FieldAttr::from_attrs(&field.attrs)?;
+        let id = field.ident.as_ref().unwrap();
+        let name = id.unraw().to_string();
+        let source = match by_name {
+            true => Source::Name(attr.rename.as_ref().map_or(name.clone(), LitStr::value)),
+            false => {
+                if let Some(span) = attr.by_name_only() {
+                    return Err(Error::new_spanned(span, "`by_name` required for this attribute"));
+                }
+                Source::Index(Index::from(idx))
+            }
+        };
+        let expr = field_expr(&attr, &name, source);
+        if attr.skip.is_none() {
+            idx += 1;
+        }
+        body.extend(quote! { #id: #expr, });
+    }
+    let mut output = quote! {};
+    Brace::default().surround(&mut output, |e|e.extend(body));
+    Ok(output)
+}
+
+/// generate enum body which match variant by tag column
+fn tagged_enum(ident: &Ident, data: DataEnum, container: Container) -> Result<TokenStream> {
+    let Some(tag) = container.tag else {
+        return Err(Error::new_spanned(data.enum_token, "enum requires `#[sql(tag = \"column\")]`"));
+    };
+    if let Some(path) = container.by_name {
+        return Err(Error::new_spanned(path, "enum variants are always decoded by name"));
+    }
+
+    let case = container.rename_all.as_ref().map(LitStr::value);
+    let mut arms = quote! {};
+
+    for variant in data.variants {
+        let id = &variant.ident;
+        let name = match crate::value::variant_rename(&variant.attrs)? {
+            Some(rename) => rename.value(),
+            None => match &case {
+                Some(case) => crate::value::rename_case(&id.to_string(), case),
+                None => id.to_string(),
+            },
+        };
+        let body = match &variant.fields {
+            Fields::Named(FieldsNamed { named, .. }) => named_fields(named, true)?,
+            Fields::Unit => quote! {},
+            Fields::Unnamed(fields) => {
+                return Err(Error::new_spanned(fields, "only struct or unit variant is supported"));
+            }
+        };
+        arms.extend(quote! { #name => Self::#id #body, });
+    }
+
+    let ty = ident.to_string();
+
+    Ok(quote! {
+        let tag = row.try_get::<&str>(#tag)?;
+        Ok(match tag {
+            #arms
+            _ => return Err(::rhosql::sqlite::error::DecodeError::UnknownVariant {
+                ty: #ty,
+                value: tag.into(),
+            }.into()),
+        })
+    })
 }
 
 pub fn from_row(input: DeriveInput) -> Result<TokenStream> {
-    let DeriveInput { attrs: _, vis: _, ident, generics, data } = input;
-    let Data::Struct(data) = data else {
-        error!("only struct are currently supported")
+    let DeriveInput { attrs, vis: _, ident, generics, data } = input;
+    let container = Container::from_attrs(&attrs)?;
+
+    let data = match data {
+        Data::Struct(data) => data,
+        Data::Enum(data) => {
+            let body = tagged_enum(&ident, data, container)?;
+            return Ok(impl_from_row(&ident, &generics, body));
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(Error::new_spanned(union_token, "union is not supported"));
+        }
     };
 
+    if let Some(tag) = container.tag {
+        return Err(Error::new_spanned(tag, "`tag` is only supported for enum"));
+    }
+    if let Some(rename_all) = container.rename_all {
+        return Err(Error::new_spanned(rename_all, "`rename_all` is only supported for enum"));
+    }
+
     let mut output = quote! {};
 
     match data.fields {
         Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-            let body = (0..unnamed.len())
-                .map(Index::from)
-                .map(|i|quote! { row.try_decode(#i)?, });
+            if let Some(path) = container.by_name {
+                return Err(Error::new_spanned(path, "`by_name` requires named fields"));
+            }
+            let mut idx = 0;
+            let mut body = quote! {};
+            for (i, field) in unnamed.iter().enumerate() {
+                let attr = FieldAttr::from_attrs(&field.attrs)?;
+                if let Some(span) = attr.by_name_only() {
+                    return Err(Error::new_spanned(span, "`by_name` required for this attribute"));
+                }
+                let expr = field_expr(&attr, &i.to_string(), Source::Index(Index::from(idx)));
+                if attr.skip.is_none() {
+                    idx += 1;
+                }
+                body.extend(quote! { #expr, });
+            }
             Paren::default().surround(&mut output, |e|e.extend(body));
         },
         Fields::Named(FieldsNamed { named, .. }) => {
-            let body = named
-                .into_iter()
-                .map(|e|e.ident.unwrap())
-                .zip(0..)
-                .map(|(e,i)|(e,Index::from(i)))
-                .map(|(id,i)|quote! { #id: row.try_decode(#i)?, });
-            Brace::default().surround(&mut output, |e|e.extend(body));
+            output = named_fields(&named, container.by_name.is_some())?;
         }
         Fields::Unit => {}
     };
 
-    let (g1, g2, g3) = generics.split_for_impl();
+    Ok(impl_from_row(&ident, &generics, quote! { Ok(Self #output) }))
+}
 
-    Ok(quote! {
-        impl #g1 ::rhosql::FromRow for #ident #g2 #g3 {
-            fn from_row(row: ::rhosql::Row) -> ::rhosql::Result<Self> {
-                Ok(Self #output)
+/// generate the impl block
+///
+/// the row lifetime reuse the lifetime declared in the type if there is only one,
+/// otherwise a new lifetime is added which outlives all of them
+fn impl_from_row(ident: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let mut impl_generics = generics.clone();
+    let mut lifetimes = generics.lifetimes();
+
+    let lt = match (lifetimes.next(), lifetimes.next()) {
+        (Some(lt), None) => lt.lifetime.clone(),
+        _ => {
+            let lt = Lifetime::new("'__r", proc_macro2::Span::call_site());
+            let mut param = LifetimeParam::new(lt.clone());
+            param.bounds.extend(generics.lifetimes().map(|e|e.lifetime.clone()));
+            impl_generics.params.insert(0, GenericParam::Lifetime(param));
+            lt
+        }
+    };
+
+    let (g1, _, g3) = impl_generics.split_for_impl();
+    let (_, g2, _) = generics.split_for_impl();
+
+    quote! {
+        impl #g1 ::rhosql::FromRow<#lt> for #ident #g2 #g3 {
+            fn from_row(row: ::rhosql::Row<#lt>) -> ::rhosql::Result<Self> {
+                #body
             }
         }
-    })
+    }
 }
-
@@ -1,8 +1,40 @@
 use proc_macro::TokenStream;
 
+mod bind;
 mod from_row;
+mod value;
 
 /// Automatically implement `FromRow` for custom struct.
+///
+/// By default, fields are decoded from columns by its position.
+///
+/// Struct with lifetime can borrow from the row, e.g: `struct PostRef<'a> { name: &'a str }`.
+///
+/// Enum is supported with `#[sql(tag = "column")]`, the variant is selected by the value of
+/// given column, and its fields are decoded by name. Unknown tag returns
+/// `DecodeError::UnknownVariant`.
+///
+/// Type which implement `Decode` is already a single column `FromRow`, so it cannot derive
+/// `FromRow` as well, e.g: a newtype deriving both `Decode` and `FromRow` does not compile.
+///
+/// # Container attributes
+///
+/// - `#[sql(by_name)]`, decode fields from columns with the same name as the field
+/// - `#[sql(tag = "column")]`, column which select the variant, requires enum
+/// - `#[sql(rename_all = "snake_case")]`, convert variant names for tag value, requires enum
+///
+/// # Variant attributes
+///
+/// - `#[sql(rename = "name")]`, use given tag value for the variant
+///
+/// # Field attributes
+///
+/// - `#[sql(rename = "name")]`, decode from column with given name, requires `by_name`
+/// - `#[sql(default)]`, use `Default` if column not found or `NULL`
+/// - `#[sql(skip)]`, use `Default` without decoding any column
+/// - `#[sql(flatten)]`, decode field which implement `FromRow` from the same row, requires `by_name`
+/// - `#[sql(prefix = "u_")]`, prefix column names for flattened field, requires `flatten`
+/// - `#[sql(try_from = "Type")]`, decode as given type, then convert via `TryFrom`
 #[proc_macro_derive(FromRow, attributes(sql))]
 pub fn from_row(input: TokenStream) -> TokenStream {
     match from_row::from_row(syn::parse_macro_input!(input as _)) {
@@ -11,3 +43,65 @@ pub fn from_row(input: TokenStream) -> TokenStream {
     }
 }
 
+
+/// Automatically implement `Decode` for custom newtype or enum.
+///
+/// - struct with single field is decoded as its inner type
+/// - enum with unit variants is decoded from TEXT of its variant name
+/// - enum with `#[repr(..)]` is decoded from INTEGER of its discriminant
+///
+/// Unknown value returns `DecodeError::UnknownVariant`.
+///
+/// # Container attributes
+///
+/// - `#[sql(rename_all = "snake_case")]`, convert variant names of TEXT enum, one of
+///   `lowercase`, `UPPERCASE`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`,
+///   `camelCase` or `PascalCase`
+///
+/// # Variant attributes
+///
+/// - `#[sql(rename = "name")]`, use given name for TEXT enum variant
+#[proc_macro_derive(Decode, attributes(sql))]
+pub fn decode(input: TokenStream) -> TokenStream {
+    match value::decode(syn::parse_macro_input!(input as _)) {
+        Ok(ok) => ok.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Automatically implement `Encode` for custom newtype or enum.
+///
+/// The implementation is generated for both `T` and `&T`.
+///
+/// It accepts the same attributes and representation as the `Decode` derive.
+#[proc_macro_derive(Encode, attributes(sql))]
+pub fn encode(input: TokenStream) -> TokenStream {
+    match value::encode(syn::parse_macro_input!(input as _)) {
+        Ok(ok) => ok.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+/// Automatically implement `Bind` for `&T` of custom struct.
+///
+/// By default, fields are bound by its position.
+///
+/// # Container attributes
+///
+/// - `#[sql(by_name)]`, bind fields to named parameter with the same name as the field, `:field`
+///
+/// # Field attributes
+///
+/// - `#[sql(rename = "name")]`, bind to parameter with given name, requires `by_name`
+/// - `#[sql(skip)]`, does not bind the field
+/// - `#[sql(flatten)]`, bind field which implement `Bind`, requires `by_name`
+/// - `#[sql(prefix = "u_")]`, prefix parameter names for flattened field, `:u_field`, requires `flatten`
+///
+/// Other `FromRow` attributes are accepted but have no effect on binding.
+#[proc_macro_derive(Bind, attributes(sql))]
+pub fn bind(input: TokenStream) -> TokenStream {
+    match bind::bind(syn::parse_macro_input!(input as _)) {
+        Ok(ok) => ok.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
@@ -0,0 +1,278 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+/// how the type is stored
+enum Repr<'a> {
+    /// single field struct, stored as the inner type
+    Transparent { ty: &'a Type, member: Member },
+    /// unit enum, stored as text of variant name
+    Text(Vec<(Ident, String)>),
+    /// unit enum with `#[repr(..)]`, stored as integer of its discriminant
+    Integer(Vec<Ident>),
+}
+
+/// container attributes, `#[sql(...)]`
+#[derive(Default)]
+struct Container {
+    /// variant name case conversion
+    rename_all: Option<LitStr>,
+}
+
+impl Container {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut me = Self::default();
+        for attr in attrs.iter().filter(|e|e.path().is_ident("sql")) {
+            attr.parse_nested_meta(|meta|{
+                if meta.path.is_ident("rename_all") {
+                    me.rename_all = Some(parse_rename_all(&meta)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown attribute"))
+                }
+            })?;
+        }
+        Ok(me)
+    }
+}
+
+/// parse `rename_all = "..."` value
+pub(crate) fn parse_rename_all(meta: &meta::ParseNestedMeta) -> Result<LitStr> {
+    let rename_all: LitStr = meta.value()?.parse()?;
+    if !RENAME_ALL.contains(&rename_all.value().as_str()) {
+        return Err(Error::new_spanned(
+            rename_all,
+            format!("expected one of {}", RENAME_ALL.join(", ")),
+        ));
+    }
+    Ok(rename_all)
+}
+
+/// variant attributes, `#[sql(...)]`
+pub(crate) fn variant_rename(attrs: &[Attribute]) -> Result<Option<LitStr>> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|e|e.path().is_ident("sql")) {
+        attr.parse_nested_meta(|meta|{
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown attribute"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
+
+const RENAME_ALL: [&str; 7] = [
+    "lowercase",
+    "UPPERCASE",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "camelCase",
+    "PascalCase",
+];
+
+/// convert PascalCase variant name into given case
+pub(crate) fn rename_case(name: &str, case: &str) -> String {
+    let mut words = vec![];
+    for ch in name.chars() {
+        if ch.is_uppercase() || words.is_empty() {
+            words.push(String::new());
+        }
+        words.last_mut().unwrap().push(ch);
+    }
+
+    let lower = words.iter().map(|e|e.to_lowercase());
+    let upper = words.iter().map(|e|e.to_uppercase());
+
+    match case {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "snake_case" => lower.collect::<Vec<_>>().join("_"),
+        "SCREAMING_SNAKE_CASE" => upper.collect::<Vec<_>>().join("_"),
+        "kebab-case" => lower.collect::<Vec<_>>().join("-"),
+        "camelCase" => {
+            let mut name = name.to_string();
+            if let Some(first) = name.get_mut(..1) {
+                first.make_ascii_lowercase();
+            }
+            name
+        }
+        _ => name.to_string(),
+    }
+}
+
+impl<'a> Repr<'a> {
+    fn from_input(input: &'a DeriveInput) -> Result<Self> {
+        let container = Container::from_attrs(&input.attrs)?;
+
+        match &input.data {
+            Data::Struct(data) => {
+                if let Some(rename_all) = container.rename_all {
+                    return Err(Error::new_spanned(rename_all, "`rename_all` is only supported for enum"));
+                }
+                let mut fields = data.fields.iter();
+                let (Some(field), None) = (fields.next(), fields.next()) else {
+                    return Err(Error::new_spanned(&input.ident, "only struct with single field is supported"));
+                };
+                let member = match &field.ident {
+                    Some(id) => Member::Named(id.clone()),
+                    None => Member::Unnamed(Index::from(0)),
+                };
+                Ok(Repr::Transparent { ty: &field.ty, member })
+            }
+            Data::Enum(data) => {
+                for variant in &data.variants {
+                    if !matches!(variant.fields, Fields::Unit) {
+                        return Err(Error::new_spanned(&variant.fields, "only unit variant is supported"));
+                    }
+                }
+
+                if input.attrs.iter().any(|e|e.path().is_ident("repr")) {
+                    if let Some(rename_all) = container.rename_all {
+                        return Err(Error::new_spanned(rename_all, "`rename_all` is not supported for integer enum"));
+                    }
+                    return Ok(Repr::Integer(data.variants.iter().map(|e|e.ident.clone()).collect()));
+                }
+
+                let case = container.rename_all.as_ref().map(LitStr::value);
+                let variants = data.variants.iter().map(|variant|{
+                    let name = match variant_rename(&variant.attrs)? {
+                        Some(rename) => rename.value(),
+                        None => match &case {
+                            Some(case) => rename_case(&variant.ident.to_string(), case),
+                            None => variant.ident.to_string(),
+                        },
+                    };
+                    Ok((variant.ident.clone(), name))
+                });
+                Ok(Repr::Text(variants.collect::<Result<_>>()?))
+            }
+            Data::Union(DataUnion { union_token, .. }) => {
+                Err(Error::new_spanned(union_token, "union is not supported"))
+            }
+        }
+    }
+}
+
+/// returns generics with the trait lifetime,
+/// reuse lifetime declared in the type if any, otherwise a new one is added
+fn with_lifetime(generics: &Generics) -> (Generics, Lifetime) {
+    let mut generics = generics.clone();
+    if let Some(lt) = generics.lifetimes().next() {
+        let lt = lt.lifetime.clone();
+        return (generics, lt);
+    }
+    let lt = Lifetime::new("'__r", proc_macro2::Span::call_site());
+    generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+    (generics, lt)
+}
+
+pub fn decode(input: DeriveInput) -> Result<TokenStream> {
+    let repr = Repr::from_input(&input)?;
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (generics, lt) = with_lifetime(&input.generics);
+    let (_, g2, _) = input.generics.split_for_impl();
+
+    let mut where_clause = generics.where_clause.clone().unwrap_or_else(||parse_quote!(where));
+
+    let body = match repr {
+        Repr::Transparent { ty, member } => {
+            where_clause.predicates.push(parse_quote!(#ty: ::rhosql::Decode<#lt>));
+            quote! {
+                <#ty as ::rhosql::Decode<#lt>>::decode(value).map(|value| Self { #member: value })
+            }
+        }
+        Repr::Text(variants) => {
+            let arms = variants.iter().map(|(id, name)| quote! { #name => Ok(Self::#id), });
+            quote! {
+                let text = <&str as ::rhosql::Decode>::decode(value)?;
+                match text {
+                    #(#arms)*
+                    _ => Err(::rhosql::sqlite::error::DecodeError::UnknownVariant {
+                        ty: #name,
+                        value: text.into(),
+                    }.into()),
+                }
+            }
+        }
+        Repr::Integer(variants) => {
+            let arms = variants.iter().map(|id| quote! { int if int == Self::#id as i64 => Ok(Self::#id), });
+            quote! {
+                let int = <i64 as ::rhosql::Decode>::decode(value)?;
+                match int {
+                    #(#arms)*
+                    _ => Err(::rhosql::sqlite::error::DecodeError::UnknownVariant {
+                        ty: #name,
+                        value: int.to_string(),
+                    }.into()),
+                }
+            }
+        }
+    };
+
+    let (g1, _, _) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #g1 ::rhosql::Decode<#lt> for #ident #g2 #where_clause {
+            fn decode(value: ::rhosql::ValueRef<#lt>) -> ::rhosql::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+pub fn encode(input: DeriveInput) -> Result<TokenStream> {
+    let repr = Repr::from_input(&input)?;
+    let ident = &input.ident;
+    let lt = Lifetime::new("'__r", proc_macro2::Span::call_site());
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+    let (g1, _, _) = generics.split_for_impl();
+    let (_, g2, _) = input.generics.split_for_impl();
+
+    let mut owned_where = generics.where_clause.clone().unwrap_or_else(||parse_quote!(where));
+    let mut ref_where = owned_where.clone();
+
+    let (owned, borrowed) = match repr {
+        Repr::Transparent { ty, member } => {
+            owned_where.predicates.push(parse_quote!(#ty: ::rhosql::Encode<#lt>));
+            ref_where.predicates.push(parse_quote!(&#lt #ty: ::rhosql::Encode<#lt>));
+            (
+                quote! { ::rhosql::Encode::encode(self.#member) },
+                quote! { ::rhosql::Encode::encode(&self.#member) },
+            )
+        }
+        Repr::Text(variants) => {
+            let arms = variants.iter().map(|(id, name)| quote! { #ident::#id => #name, });
+            let body = quote! {
+                Ok(::rhosql::Encoded::Borrowed(::rhosql::ValueRef::Text(match self { #(#arms)* })))
+            };
+            (body.clone(), body)
+        }
+        Repr::Integer(variants) => {
+            let arms = variants.iter().map(|id| quote! { #ident::#id => #ident::#id as i64, });
+            let body = quote! {
+                Ok(::rhosql::Encoded::Borrowed(::rhosql::ValueRef::Int(match self { #(#arms)* })))
+            };
+            (body.clone(), body)
+        }
+    };
+
+    Ok(quote! {
+        impl #g1 ::rhosql::Encode<#lt> for #ident #g2 #owned_where {
+            fn encode(self) -> ::rhosql::Result<::rhosql::Encoded<#lt>> {
+                #owned
+            }
+        }
+
+        impl #g1 ::rhosql::Encode<#lt> for &#lt #ident #g2 #ref_where {
+            fn encode(self) -> ::rhosql::Result<::rhosql::Encoded<#lt>> {
+                #borrowed
+            }
+        }
+    })
+}
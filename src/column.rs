@@ -0,0 +1,137 @@
+use crate::sqlite::StatementExt;
+
+/// Metadata of result columns of a statement.
+///
+/// Available before the first row is fetched, obtained from
+/// [`RowStream::columns`][crate::RowStream::columns] or [`Row::columns`][crate::Row::columns].
+///
+/// Table related metadata is `None` when the result column is an expression or subquery, and
+/// requires `column_metadata` feature.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// # let mut db = Connection::open_in_memory()?;
+/// # rhosql::query("create table post(name text)", &mut db).execute()?;
+/// let rows = rhosql::query("select name as title, 420 as id from post", &mut db).fetch()?;
+/// let columns = rows.columns();
+///
+/// assert_eq!(columns.len(), 2);
+///
+/// let title = columns.get(0).unwrap();
+/// assert_eq!(title.name(), Some("title"));
+/// assert_eq!(title.decltype(), Some("TEXT"));
+///
+/// let id = columns.get(1).unwrap();
+/// assert_eq!(id.name(), Some("id"));
+/// assert_eq!(id.decltype(), None);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Columns<'a> {
+    handle: &'a *mut libsqlite3_sys::sqlite3_stmt,
+    count: i32,
+}
+
+impl<'a> Columns<'a> {
+    pub(crate) fn new(handle: &'a *mut libsqlite3_sys::sqlite3_stmt) -> Self {
+        Self {
+            count: handle.column_count(),
+            handle,
+        }
+    }
+
+    /// Returns column at given index.
+    pub fn get(&self, idx: usize) -> Option<Column<'a>> {
+        match idx < self.len() {
+            true => Some(Column { handle: self.handle, idx: idx as _ }),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator of columns.
+    pub fn iter(&self) -> impl Iterator<Item = Column<'a>> {
+        let me = *self;
+        (0..self.len()).filter_map(move |idx| me.get(idx))
+    }
+
+    /// Returns the column count.
+    pub fn len(&self) -> usize {
+        self.count as _
+    }
+
+    /// Returns `true` if the statement returns no column.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Metadata of a single result column.
+#[derive(Debug, Clone, Copy)]
+pub struct Column<'a> {
+    handle: &'a *mut libsqlite3_sys::sqlite3_stmt,
+    idx: i32,
+}
+
+impl<'a> Column<'a> {
+    /// Returns the column index.
+    pub fn index(&self) -> usize {
+        self.idx as _
+    }
+
+    /// Returns the column name, which is the value of "AS" clause if any.
+    pub fn name(&self) -> Option<&'a str> {
+        self.handle.column_name(self.idx)
+    }
+
+    /// Returns the declared type of the origin table column.
+    pub fn decltype(&self) -> Option<&'a str> {
+        self.handle.column_decltype(self.idx)
+    }
+
+    /// Returns the database name of the origin table.
+    ///
+    /// Requires `column_metadata` feature.
+    #[cfg(feature = "column_metadata")]
+    pub fn database_name(&self) -> Option<&'a str> {
+        self.handle.column_database_name(self.idx)
+    }
+
+    /// Returns the name of the origin table.
+    ///
+    /// Requires `column_metadata` feature.
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// # rhosql::query("create table post(name text)", &mut db).execute()?;
+    /// let rows = rhosql::query("select name as title, 420 as id from post", &mut db).fetch()?;
+    /// let columns = rows.columns();
+    ///
+    /// let title = columns.get(0).unwrap();
+    /// assert_eq!(title.database_name(), Some("main"));
+    /// assert_eq!(title.table_name(), Some("post"));
+    /// assert_eq!(title.origin_name(), Some("name"));
+    ///
+    /// assert_eq!(columns.get(1).unwrap().table_name(), None);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "column_metadata")]
+    pub fn table_name(&self) -> Option<&'a str> {
+        self.handle.column_table_name(self.idx)
+    }
+
+    /// Returns the name of the origin table column.
+    ///
+    /// Requires `column_metadata` feature.
+    #[cfg(feature = "column_metadata")]
+    pub fn origin_name(&self) -> Option<&'a str> {
+        self.handle.column_origin_name(self.idx)
+    }
+}
@@ -8,6 +8,7 @@ use crate::Result;
 use crate::sqlite::error::StringError;
 
 pub(crate) mod stack;
+pub(crate) mod spill;
 
 pub(crate) mod sealed {
     pub trait Sealed {}
@@ -0,0 +1,59 @@
+use super::stack::Stack;
+
+/// List which stack allocated until its full, then spilled to heap.
+pub enum SpillVec<T, const S: usize> {
+    Stack(Stack<T, S>),
+    Heap(Vec<T>),
+}
+
+impl<T, const S: usize> SpillVec<T, S> {
+    /// Constructs a new, empty [`SpillVec<T>`] with specified stack capacity.
+    pub fn new() -> Self {
+        Self::Stack(Stack::with_size())
+    }
+
+    /// Appends an element to the back of the list, spill to heap if stack capacity is full.
+    pub fn push(&mut self, item: T) {
+        let stack = match self {
+            SpillVec::Stack(stack) => stack,
+            SpillVec::Heap(vec) => return vec.push(item),
+        };
+
+        let Some(item) = stack.push(item) else {
+            return;
+        };
+
+        let mut vec = Vec::with_capacity(S * 2);
+
+        // `Stack` only pop from the back
+        while let Some(item) = stack.pop() {
+            vec.push(item);
+        }
+        vec.reverse();
+        vec.push(item);
+
+        *self = SpillVec::Heap(vec);
+    }
+
+    /// Extracts a slice containing the entire list.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            SpillVec::Stack(stack) => stack.as_slice(),
+            SpillVec::Heap(vec) => vec.as_slice(),
+        }
+    }
+}
+
+impl<T, const S: usize> std::ops::Deref for SpillVec<T, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: std::fmt::Debug, const S: usize> std::fmt::Debug for SpillVec<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
@@ -8,7 +8,11 @@ use crate::{
     Result,
     common::SqliteStr,
     query::{Execute, StatementRef},
-    sqlite::{Database, DatabaseExt, OpenFlag, SqliteHandle, StatementHandle, error::OpenError},
+    sqlite::{
+        BlobHandle, Database, DatabaseExt, OpenFlag, SqliteHandle, StatementHandle,
+        error::{BlobError, OpenError},
+    },
+    transaction::{Savepoint, Transaction, TransactionBehavior},
 };
 
 /// Database connection.
@@ -56,6 +60,81 @@ impl Connection {
             stmts: LruCache::new(NonZeroUsize::new(24).unwrap()),
         })
     }
+
+    /// Execute sql which may contain multiple statements, e.g: schema file.
+    ///
+    /// Statements are not cached. Note that [`query`][crate::query()] only accept single statement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// let mut db = Connection::open_in_memory()?;
+    ///
+    /// db.execute_batch("
+    ///     create table post(name);
+    ///     -- initial data
+    ///     insert into post(name) values('Control');
+    /// ")?;
+    ///
+    /// let err = db.execute_batch("insert into post(name) values('Vanish'); select * from noop;");
+    /// assert!(matches!(err, Err(rhosql::Error::Batch(err)) if err.offset == 41));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn execute_batch<S: SqliteStr>(&mut self, sql: S) -> Result<()> {
+        DatabaseExt::execute_batch(self, sql)?;
+        Ok(())
+    }
+
+    /// Open a blob handle for incremental I/O at given table, column and rowid in main database.
+    ///
+    /// The blob handle borrows the connection, so it cannot outlive it.
+    ///
+    /// ```compile_fail,E0597
+    /// # use rhosql::Connection;
+    /// let blob = {
+    ///     let db = Connection::open_in_memory().unwrap();
+    ///     db.open_blob("file", "data", 1, false).unwrap()
+    /// };
+    /// ```
+    ///
+    /// See [`ZeroBlob`][crate::ZeroBlob] for example.
+    pub fn open_blob<T: SqliteStr, C: SqliteStr>(
+        &self,
+        table: T,
+        column: C,
+        rowid: i64,
+        writable: bool,
+    ) -> Result<BlobHandle<'_>> {
+        let table = table.to_nul_string().map_err(BlobError::from)?;
+        let column = column.to_nul_string().map_err(BlobError::from)?;
+        Ok(BlobHandle::open(self, c"main", &table, &column, rowid, writable)?)
+    }
+
+    /// Begin a deferred transaction.
+    ///
+    /// See [`Transaction`] for more details.
+    pub fn transaction(&mut self) -> Result<Transaction<'_>> {
+        Transaction::begin(self, TransactionBehavior::Deferred)
+    }
+
+    /// Begin a transaction with given behavior.
+    ///
+    /// See [`Transaction`] for more details.
+    pub fn transaction_with(&mut self, behavior: TransactionBehavior) -> Result<Transaction<'_>> {
+        Transaction::begin(self, behavior)
+    }
+
+    /// Create a savepoint.
+    ///
+    /// Savepoint outside a transaction behave like a deferred transaction.
+    ///
+    /// See [`Savepoint`] for more details.
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        Savepoint::begin(self, 1)
+    }
 }
 
 impl<'s> Execute<'s> for &'s mut Connection {
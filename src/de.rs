@@ -0,0 +1,411 @@
+//! Integration with [`serde`] deserialization, requires `serde` feature.
+//!
+//! Row is deserialized as a map of column name to its value, or a sequence of values when
+//! tuple or sequence is requested. Single column row can also be deserialized as the column
+//! value, e.g: `i64` or `String`.
+//!
+//! Column value is deserialized according to the requested type:
+//!
+//! - `NULL` as `None` or unit
+//! - INTEGER, REAL, TEXT and BLOB as the corresponding primitive, with the same range
+//!   checking and [coercion][crate::Row::coerce] as [`Decode`][crate::Decode]
+//! - `bool` from INTEGER `0` or `1`
+//! - unit enum variant from TEXT
+//! - nested map, struct or sequence from TEXT as json text, or BLOB as sqlite JSONB
+//! - sequence from BLOB that is not a well formed JSONB array as bytes, e.g: `Vec<u8>`
+//!
+//! Error is reported as [`DecodeError::Column`] containing the column name.
+//!
+//! # Example
+//!
+//! ```
+//! # fn main() -> rhosql::Result<()> {
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Post<'a> {
+//!     id: i64,
+//!     name: &'a str,
+//!     content: Option<String>,
+//!     tags: Vec<String>,
+//! }
+//!
+//! let mut db = rhosql::Connection::open_in_memory()?;
+//!
+//! let err = rhosql::query("select 'Control' as id", &mut db).fetch_all_de::<(i64,)>().unwrap_err();
+//! assert!(err.to_string().contains("column `id`"));
+//!
+//! let mut rows = rhosql::query("select 420 as id, 'Control' as name, null as content, ?1 as tags", &mut db)
+//!     .bind(r#"["rust","sqlite"]"#)
+//!     .fetch()?;
+//!
+//! let post = rows.next_row_de::<Post>()?.unwrap();
+//! assert_eq!(post.id, 420);
+//! assert_eq!(post.name, "Control");
+//! assert_eq!(post.content, None);
+//! assert_eq!(post.tags, ["rust", "sqlite"]);
+//! drop(rows);
+//!
+//! // `x'2b1761'` is the JSONB of `["a"]`
+//! let rows = rhosql::query("select x'2b1761', x'0102'", &mut db).fetch_all_de::<(Vec<String>, Vec<u8>)>()?;
+//! assert_eq!(rows[0].0, ["a"]);
+//! assert_eq!(rows[0].1, [1, 2]);
+//!
+//! let err = rhosql::query("select x'0102' as tags", &mut db).fetch_all_de::<(Vec<String>,)>().unwrap_err();
+//! assert!(err.to_string().contains("blob is read as bytes"));
+//! #   Ok(())
+//! # }
+//! ```
+use serde::de::{
+    self, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    value::{BorrowedStrDeserializer, SeqDeserializer},
+};
+
+use crate::{
+    Error, FromRow, Result, Row, ValueRef,
+    sqlite::{DataType, error::DecodeError},
+    types::json::jsonb,
+};
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        DecodeError::Custom(msg.to_string()).into()
+    }
+}
+
+/// Deserialize a row into any [`Deserialize`][de::Deserialize] type.
+pub fn from_row<'r, T: de::Deserialize<'r>>(row: Row<'r>) -> Result<T> {
+    T::deserialize(RowDeserializer::new(row))
+}
+
+/// [`FromRow`] implementation via [`Deserialize`][de::Deserialize], used in `*_de` methods
+pub(crate) struct Deserialized<T>(pub T);
+
+impl<'r, T: de::Deserialize<'r>> FromRow<'r> for Deserialized<T> {
+    fn from_row(row: Row<'r>) -> Result<Self> {
+        from_row(row).map(Self)
+    }
+}
+
+// NOTE: row
+
+/// A [`Deserializer`] of a row.
+#[derive(Debug)]
+pub struct RowDeserializer<'r> {
+    row: Row<'r>,
+}
+
+impl<'r> RowDeserializer<'r> {
+    pub fn new(row: Row<'r>) -> Self {
+        Self { row }
+    }
+
+    /// single column row can be deserialized as its value
+    fn value(&self) -> ValueDeserializer<'_, 'r> {
+        ValueDeserializer { row: &self.row, idx: 0 }
+    }
+
+    fn access(&self) -> RowAccess<'_, 'r> {
+        RowAccess { row: &self.row, idx: 0 }
+    }
+}
+
+macro_rules! forward_to_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+                let value = self.value();
+                value.with_column(value.$method(visitor))
+            }
+        )*
+    };
+}
+
+impl<'r> Deserializer<'r> for RowDeserializer<'r> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(self.access())
+    }
+
+    fn deserialize_map<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(self.access())
+    }
+
+    fn deserialize_struct<V: Visitor<'r>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_map(self.access())
+    }
+
+    fn deserialize_seq<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(self.access())
+    }
+
+    fn deserialize_tuple<V: Visitor<'r>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(self.access())
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'r>>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(self.access())
+    }
+
+    fn deserialize_option<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'r>>(self, _: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'r>>(self, name: &'static str, visitor: V) -> Result<V::Value> {
+        let value = self.value();
+        value.with_column(value.deserialize_unit_struct(name, visitor))
+    }
+
+    fn deserialize_enum<V: Visitor<'r>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let value = self.value();
+        value.with_column(value.deserialize_enum(name, variants, visitor))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    forward_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_unit deserialize_identifier
+    }
+}
+
+/// [`MapAccess`] and [`SeqAccess`] of row columns.
+struct RowAccess<'a, 'r> {
+    row: &'a Row<'r>,
+    idx: i32,
+}
+
+impl<'a, 'r> RowAccess<'a, 'r> {
+    /// returns the next column, columns with duplicate name is skipped in map access,
+    /// the same as [`Row::try_get`]
+    fn next_column(&mut self, skip_duplicate: bool) -> Option<ValueDeserializer<'a, 'r>> {
+        while (self.idx as usize) < self.row.len() {
+            let idx = self.idx;
+            self.idx += 1;
+            let name = self.row.column_name(idx);
+            if skip_duplicate && (0..idx).any(|prev| self.row.column_name(prev) == name) {
+                continue;
+            }
+            return Some(ValueDeserializer { row: self.row, idx });
+        }
+        None
+    }
+}
+
+impl<'r> MapAccess<'r> for RowAccess<'_, 'r> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'r>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.next_column(true) {
+            Some(value) => seed.deserialize(value.name().into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'r>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = ValueDeserializer { row: self.row, idx: self.idx - 1 };
+        value.with_column(seed.deserialize(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.idx as usize)
+    }
+}
+
+impl<'r> SeqAccess<'r> for RowAccess<'_, 'r> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'r>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.next_column(false) {
+            Some(value) => value.with_column(seed.deserialize(value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.idx as usize)
+    }
+}
+
+// NOTE: value
+
+/// A [`Deserializer`] of a single column.
+#[derive(Clone, Copy)]
+struct ValueDeserializer<'a, 'r> {
+    row: &'a Row<'r>,
+    idx: i32,
+}
+
+impl<'r> ValueDeserializer<'_, 'r> {
+    fn name(&self) -> String {
+        match self.row.column_name(self.idx) {
+            Some(name) => name.into(),
+            None => self.idx.to_string(),
+        }
+    }
+
+    fn column(&self) -> Result<ValueRef<'r>> {
+        Ok(self.row.try_column(self.idx)?)
+    }
+
+    /// add column context to decode error
+    fn with_column<T>(&self, result: Result<T>) -> Result<T> {
+        result.map_err(|err| match err {
+            Error::Decode(DecodeError::Column { .. }) => err,
+            Error::Decode(error) => DecodeError::Column { name: self.name(), error: Box::new(error) }.into(),
+            err => err,
+        })
+    }
+
+    /// nested value is stored as json
+    fn json<V: Visitor<'r>>(&self, visitor: V) -> Result<V::Value> {
+        let result = match self.column()? {
+            ValueRef::Text(text) => {
+                let mut de = serde_json::Deserializer::from_str(text);
+                de.deserialize_any(visitor).and_then(|value| de.end().map(|_| value))
+            }
+            ValueRef::Blob(blob) => jsonb::decode(blob)?.deserialize_any(visitor),
+            value => Err(DecodeError::InvalidDataType { expect: DataType::Text, found: value.data_type() })?,
+        };
+        result.map_err(|err| DecodeError::Custom(format!("failed to decode json: {err}")).into())
+    }
+}
+
+macro_rules! decode {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+                visitor.$visit(self.row.try_decode(self.idx)?)
+            }
+        )*
+    };
+}
+
+impl<'r> Deserializer<'r> for ValueDeserializer<'_, 'r> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        match self.column()? {
+            ValueRef::Null => visitor.visit_unit(),
+            ValueRef::Int(int) => visitor.visit_i64(int),
+            ValueRef::Float(float) => visitor.visit_f64(float),
+            ValueRef::Text(text) => visitor.visit_borrowed_str(text),
+            ValueRef::Blob(blob) => visitor.visit_borrowed_bytes(blob),
+        }
+    }
+
+    decode! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+        deserialize_str => visit_borrowed_str,
+        deserialize_string => visit_borrowed_str,
+        deserialize_identifier => visit_borrowed_str,
+        deserialize_bytes => visit_borrowed_bytes,
+        deserialize_byte_buf => visit_borrowed_bytes,
+    }
+
+    fn deserialize_unit<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        self.row.try_decode::<()>(self.idx)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        match self.column()? {
+            ValueRef::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'r>>(self, _: &'static str, visitor: V) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'r>>(self, _: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        match self.column()? {
+            ValueRef::Blob(blob) => match jsonb::decode_array(blob) {
+                Some(array) => array
+                    .deserialize_any(visitor)
+                    .map_err(|err| DecodeError::Custom(format!("failed to decode json: {err}")).into()),
+                None => {
+                    let mut seq = SeqDeserializer::<_, Error>::new(blob.iter().copied());
+                    let result = visitor.visit_seq(&mut seq).and_then(|value| seq.end().map(|_| value));
+                    result.map_err(|err| match err {
+                        Error::Decode(DecodeError::Custom(msg)) => {
+                            DecodeError::Custom(format!("blob is read as bytes: {msg}")).into()
+                        }
+                        err => err,
+                    })
+                }
+            },
+            _ => self.json(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'r>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'r>>(self, _: &'static str, _: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'r>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.json(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'r>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let text = self.row.try_decode::<&str>(self.idx)?;
+        visitor.visit_enum(BorrowedStrDeserializer::new(text))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'r>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
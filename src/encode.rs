@@ -0,0 +1,384 @@
+use std::{
+    borrow::Cow,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64,
+    },
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    Result, Value, ValueRef,
+    sqlite::{
+        Statement, StatementExt,
+        error::{BindError, StringError},
+    },
+};
+
+/// A type that can be bound as sqlite parameter.
+///
+/// This is the binding counterpart of [`Decode`][crate::Decode].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use rhosql::{Connection, Encode, Encoded};
+///
+/// struct UserName(String);
+///
+/// impl<'a> Encode<'a> for &'a UserName {
+///     fn encode(self) -> rhosql::Result<Encoded<'a>> {
+///         self.0.as_str().encode()
+///     }
+/// }
+///
+/// let mut db = Connection::open_in_memory()?;
+/// let name = UserName("Control".into());
+///
+/// let row = rhosql::query("select ?1", &mut db).bind(&name).fetch_optional::<(String,)>()?;
+/// assert_eq!(row, Some(("Control".into(),)));
+/// #   Ok(())
+/// # }
+/// ```
+pub trait Encode<'a> {
+    fn encode(self) -> Result<Encoded<'a>>;
+}
+
+/// An encoded value which ready to be bound as parameter.
+#[derive(Debug)]
+pub enum Encoded<'a> {
+    /// A borrowed value.
+    Borrowed(ValueRef<'a>),
+    /// An owned value.
+    Owned(Value),
+    /// A blob of given length filled with zeroes.
+    ZeroBlob(u64),
+}
+
+impl Encoded<'_> {
+    /// Bind current value to parameter at given index.
+    ///
+    /// Note that parameter index is one based.
+    pub fn bind<S: Statement>(&self, idx: i32, handle: S) -> Result<(), BindError> {
+        match self {
+            Encoded::Borrowed(value) => value.bind(idx, handle),
+            Encoded::Owned(value) => value.as_ref().bind(idx, handle),
+            Encoded::ZeroBlob(len) => handle.bind_zeroblob(idx, *len),
+        }
+    }
+}
+
+macro_rules! encode {
+    ($ty:ty, $me:ident => $expr:expr) => {
+        impl<'a> Encode<'a> for $ty {
+            fn encode(self) -> Result<Encoded<'a>> {
+                let $me = self;
+                Ok(Encoded::Borrowed($expr))
+            }
+        }
+        impl<'a> Encode<'a> for &$ty {
+            fn encode(self) -> Result<Encoded<'a>> {
+                <$ty as Encode>::encode(*self)
+            }
+        }
+    };
+}
+
+encode!((), _me => ValueRef::Null);
+encode!(bool, me => ValueRef::Int(me as _));
+encode!(i8, me => ValueRef::Int(me.into()));
+encode!(i16, me => ValueRef::Int(me.into()));
+encode!(i32, me => ValueRef::Int(me.into()));
+encode!(i64, me => ValueRef::Int(me));
+encode!(u8, me => ValueRef::Int(me.into()));
+encode!(u16, me => ValueRef::Int(me.into()));
+encode!(u32, me => ValueRef::Int(me.into()));
+encode!(f32, me => ValueRef::Float(me.into()));
+encode!(f64, me => ValueRef::Float(me));
+
+impl<'a> Encode<'a> for u64 {
+    fn encode(self) -> Result<Encoded<'a>> {
+        match i64::try_from(self) {
+            Ok(int) => int.encode(),
+            Err(_) => Err(BindError::Overflow { ty: "u64" }.into()),
+        }
+    }
+}
+
+impl<'a> Encode<'a> for &u64 {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (*self).encode()
+    }
+}
+
+macro_rules! encode_owned {
+    ($($ty:ty),* => $me:ident => $expr:expr) => {
+        $(
+            impl<'a> Encode<'a> for $ty {
+                fn encode(self) -> Result<Encoded<'a>> {
+                    (&self).encode()
+                }
+            }
+            impl<'a> Encode<'a> for &$ty {
+                fn encode(self) -> Result<Encoded<'a>> {
+                    let $me = self;
+                    Ok(Encoded::Owned($expr))
+                }
+            }
+        )*
+    };
+}
+
+encode_owned!(char, IpAddr, Ipv4Addr, Ipv6Addr => me => Value::Text(me.to_string()));
+
+macro_rules! encode_non_zero {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a> Encode<'a> for $ty {
+                fn encode(self) -> Result<Encoded<'a>> {
+                    self.get().encode()
+                }
+            }
+            impl<'a> Encode<'a> for &$ty {
+                fn encode(self) -> Result<Encoded<'a>> {
+                    self.get().encode()
+                }
+            }
+        )*
+    };
+}
+
+encode_non_zero!(
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64,
+    NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64
+);
+
+/// Stored as INTEGER nanoseconds, which is roughly up to 292 years.
+impl<'a> Encode<'a> for Duration {
+    fn encode(self) -> Result<Encoded<'a>> {
+        match i64::try_from(self.as_nanos()) {
+            Ok(nanos) => nanos.encode(),
+            Err(_) => Err(BindError::Overflow { ty: "Duration" }.into()),
+        }
+    }
+}
+
+impl<'a> Encode<'a> for &Duration {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (*self).encode()
+    }
+}
+
+/// Stored as INTEGER unix timestamp in nanoseconds, which is roughly year 1677 to 2262.
+impl<'a> Encode<'a> for SystemTime {
+    fn encode(self) -> Result<Encoded<'a>> {
+        let nanos = match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => i128::try_from(after.as_nanos()),
+            Err(before) => i128::try_from(before.duration().as_nanos()).map(|nanos| -nanos),
+        };
+        match nanos.ok().and_then(|nanos| i64::try_from(nanos).ok()) {
+            Some(nanos) => nanos.encode(),
+            None => Err(BindError::Overflow { ty: "SystemTime" }.into()),
+        }
+    }
+}
+
+impl<'a> Encode<'a> for &SystemTime {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (*self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for ValueRef<'a> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Borrowed(self))
+    }
+}
+
+impl<'a> Encode<'a> for &ValueRef<'a> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Borrowed(*self))
+    }
+}
+
+impl<'a> Encode<'a> for &'a str {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Borrowed(ValueRef::Text(self)))
+    }
+}
+
+impl<'a> Encode<'a> for &&'a str {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (*self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for &'a String {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.as_str().encode()
+    }
+}
+
+impl<'a> Encode<'a> for String {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Owned(Value::Text(self)))
+    }
+}
+
+impl<'a> Encode<'a> for &'a Box<str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (**self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for Box<str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        String::from(self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for &'a Arc<str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (**self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for Arc<str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.to_string().encode()
+    }
+}
+
+impl<'a> Encode<'a> for &'a Cow<'_, str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (**self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for Cow<'a, str> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        match self {
+            Cow::Borrowed(text) => text.encode(),
+            Cow::Owned(text) => text.encode(),
+        }
+    }
+}
+
+/// path is stored as TEXT, so it must be valid UTF-8
+fn path_str(path: &Path) -> Result<&str, BindError> {
+    std::str::from_utf8(path.as_os_str().as_encoded_bytes()).map_err(|err| StringError::from(err).into())
+}
+
+/// Stored as TEXT, returns error if the path is not valid UTF-8.
+impl<'a> Encode<'a> for &'a Path {
+    fn encode(self) -> Result<Encoded<'a>> {
+        path_str(self)?.encode()
+    }
+}
+
+impl<'a> Encode<'a> for &'a PathBuf {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.as_path().encode()
+    }
+}
+
+impl<'a> Encode<'a> for PathBuf {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Owned(Value::Text(path_str(&self)?.into())))
+    }
+}
+
+impl<'a> Encode<'a> for &'a [u8] {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Borrowed(ValueRef::Blob(self)))
+    }
+}
+
+impl<'a> Encode<'a> for &&'a [u8] {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (*self).encode()
+    }
+}
+
+impl<'a> Encode<'a> for &'a Vec<u8> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.as_slice().encode()
+    }
+}
+
+impl<'a> Encode<'a> for Vec<u8> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Owned(Value::Blob(self)))
+    }
+}
+
+impl<'a, const N: usize> Encode<'a> for &'a [u8; N] {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.as_slice().encode()
+    }
+}
+
+impl<'a, const N: usize> Encode<'a> for [u8; N] {
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.to_vec().encode()
+    }
+}
+
+/// A blob of given length filled with zeroes.
+///
+/// Used to reserve blob size which then written incrementally via
+/// [`BlobHandle`][crate::sqlite::BlobHandle].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use std::io::{Read, Write};
+/// use rhosql::{Connection, ZeroBlob};
+///
+/// let mut db = Connection::open_in_memory()?;
+/// rhosql::query("create table file(data)", &mut db).execute()?;
+///
+/// let id = rhosql::query("insert into file(data) values(?)", &mut db)
+///     .bind(ZeroBlob(5))
+///     .execute()?.last_insert_rowid;
+///
+/// let mut blob = db.open_blob("file", "data", id, true)?;
+/// blob.write_all(b"hello").unwrap();
+/// drop(blob);
+///
+/// let mut data = String::new();
+/// db.open_blob("file", "data", id, false)?.read_to_string(&mut data).unwrap();
+/// assert_eq!(data, "hello");
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroBlob(pub u64);
+
+impl<'a> Encode<'a> for ZeroBlob {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::ZeroBlob(self.0))
+    }
+}
+
+impl<'a, T: Encode<'a>> Encode<'a> for Option<T> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        match self {
+            Some(value) => value.encode(),
+            None => Ok(Encoded::Borrowed(ValueRef::Null)),
+        }
+    }
+}
+
+impl<'a, T> Encode<'a> for &'a Option<T>
+where
+    &'a T: Encode<'a>,
+{
+    fn encode(self) -> Result<Encoded<'a>> {
+        self.as_ref().encode()
+    }
+}
@@ -1,7 +1,7 @@
 //! An error which can occur in sqlite operation.
 use crate::sqlite::error::{
-    BindError, ConfigureError, DecodeError, OpenError, PrepareError, ResetError, StepError,
-    display_error, from,
+    BatchError, BindError, BlobError, ConfigureError, DecodeError, OpenError, PrepareError, ResetError,
+    StepError, display_error, from,
 };
 
 /// Represent success or error for sqlite operation.
@@ -23,6 +23,10 @@ pub enum Error {
     Decode(DecodeError),
     /// an error when failed to reset or clear binding prepared statement
     Reset(ResetError),
+    /// an error when failed to execute multiple statements
+    Batch(BatchError),
+    /// an error when failed to access blob
+    Blob(BlobError),
 }
 
 from! {
@@ -33,11 +37,13 @@ from! {
     for BindError => Bind,
     for StepError => Step,
     for DecodeError => Decode,
-    for ResetError => Reset
+    for ResetError => Reset,
+    for BatchError => Batch,
+    for BlobError => Blob
 }
 
 display_error! {
     Error,
-    #delegate Open Configure Prepare Bind Step Decode Reset
+    #delegate Open Configure Prepare Bind Step Decode Reset Batch Blob
 }
 
@@ -11,9 +11,9 @@
 //! use rhosql::Connection;
 //!
 //! // derive macro
-//! #[derive(rhosql::FromRow)]
+//! #[derive(Debug, rhosql::FromRow)]
 //! struct Post {
-//!     id: i32,
+//!     id: i64,
 //!     name: String,
 //! }
 //!
@@ -24,25 +24,25 @@
 //!
 //! let id = rhosql::query("insert into post(name) values(?1)", &mut db)
 //!     .bind("Control")
-//!     .execute()?;
+//!     .execute()?.last_insert_rowid;
 //!
 //! // using custom struct
 //! let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<Post>()?;
 //!
-//! assert_eq!(posts[0].id as i64, id);
+//! assert_eq!(posts[0].id, id);
 //! assert_eq!(posts[0].name, "Control");
 //!
 //! // using tuple
-//! let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i32, String)>()?;
+//! let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i64, String)>()?;
 //!
-//! assert_eq!(posts[0].0 as i64, id);
+//! assert_eq!(posts[0].0, id);
 //! assert_eq!(posts[0].1, "Control");
 //!
 //! // iterator based
 //! let mut posts = rhosql::query("select rowid,* from post", &mut db).fetch()?;
 //!
 //! while let Some(post) = posts.next_row::<Post>()? {
-//!     assert_eq!(post.id as i64, id);
+//!     assert_eq!(post.id, id);
 //!     assert_eq!(post.name, "Control");
 //! }
 //! #   Ok(())
@@ -60,12 +60,21 @@ pub mod query;
 
 // shared state
 mod connection;
+mod transaction;
 mod serialize;
 mod pool;
 
 // subtypes
 mod row_stream;
 mod row;
+mod column;
+mod value;
+mod encode;
+pub mod types;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "serde")]
+pub mod ser;
 
 // error
 mod error;
@@ -77,9 +86,13 @@ pub use query::query;
 
 pub use common::SqliteStr;
 pub use connection::Connection;
+pub use transaction::{Savepoint, Transaction, TransactionBehavior};
 pub use serialize::SerializeConnection;
 pub use row_stream::RowStream;
-pub use row::{Decode, FromRow, Row, ValueRef};
-pub use rhosql_macros::FromRow;
+pub use row::{Decode, DynamicRow, FromRow, Row, ValueRef};
+pub use column::{Column, Columns};
+pub use value::Value;
+pub use encode::{Encode, Encoded, ZeroBlob};
+pub use rhosql_macros::{Bind, Decode, Encode, FromRow};
 pub use error::{Result, Error};
 
@@ -1,11 +1,16 @@
 //! Types for query api.
 
+use std::borrow::Cow;
+
 use crate::{
-    FromRow, Result, Row, SqliteStr, ValueRef,
-    common::stack::Stack,
+    Encode, Encoded, Error, FromRow, Result, Row, SqliteStr,
+    common::spill::SpillVec,
+    row::RowState,
     row_stream::RowStream,
     sqlite::{
         Database, DatabaseExt, SqliteHandle, Statement, StatementExt, StatementHandle, StepResult,
+        error::{BindError, StringError},
+        is_insert,
     },
 };
 
@@ -45,9 +50,9 @@ impl Statement for StatementRef<'_> {
 /// # fn main() -> rhosql::Result<()> {
 /// # use rhosql::Connection;
 /// # let mut db = Connection::open_in_memory()?;
-/// #[derive(rhosql::FromRow)]
+/// #[derive(Debug, rhosql::FromRow)]
 /// struct Post {
-///     id: i32,
+///     id: i64,
 ///     name: String,
 /// }
 ///
@@ -56,14 +61,12 @@ impl Statement for StatementRef<'_> {
 ///     .bind("Control")
 ///     .execute()?;
 ///
-/// let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i32, String)>()?;
+/// let posts = rhosql::query("select rowid,* from post", &mut db).fetch_all::<(i64, String)>()?;
 /// #   Ok(())
 /// # }
 /// ```
-///
-/// Note that parameter [`bind`][Query::bind] have hard limit of 16.
 pub fn query<'a, 's, S: SqliteStr, E: Execute<'s>>(sql: S, db: E) -> Query<'a, S, E> {
-    Query { db, sql, params: Stack::with_size() }
+    Query { db, sql, params: SpillVec::new(), error: None, coerce: false, prefix: String::new() }
 }
 
 /// Query api created by [`query`]
@@ -71,36 +74,357 @@ pub fn query<'a, 's, S: SqliteStr, E: Execute<'s>>(sql: S, db: E) -> Query<'a, S
 pub struct Query<'a, S, E> {
     db: E,
     sql: S,
-    params: Stack<ValueRef<'a>,16>,
+    params: SpillVec<Param<'a>,16>,
+    /// the first error when encoding parameter, returned on execution
+    error: Option<Error>,
+    /// enable type coercion for returned rows
+    coerce: bool,
+    /// prefix of parameter names, used in [`Query::bind_all_prefixed`]
+    prefix: String,
+}
+
+/// A type which bind multiple parameters at once, used in [`Query::bind_all`].
+///
+/// Usually derived via `#[derive(Bind)]`, which implement it for `&T`.
+pub trait Bind<'a> {
+    fn bind_all<S, E>(self, query: Query<'a, S, E>) -> Query<'a, S, E>;
+}
+
+/// A parameter which bound by position or by name.
+#[derive(Debug)]
+struct Param<'a> {
+    name: Option<Cow<'a, str>>,
+    value: Encoded<'a>,
 }
 
 impl<'a, S, E> Query<'a, S, E> {
-    /// Bind a parameter.
+    /// Bind a parameter by position.
+    ///
+    /// Positional parameter is bound to the next parameter which is not bound by
+    /// [`bind_named`][Query::bind_named], whether the parameter is named or not, so both
+    /// can be mixed.
+    ///
+    /// Numbered parameter `?NNN` is bound in order of its number, unused numbers are skipped.
+    /// Because sqlite cannot tell unused number from anonymous `?`, anonymous `?` cannot be
+    /// mixed with `?NNN`.
+    ///
+    /// Error when encoding the value is returned on execution.
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// # use rhosql::sqlite::error::BindError;
+    /// let row = rhosql::query("select ?1, ?3, ?1", &mut db)
+    ///     .bind(420)
+    ///     .bind("Control")
+    ///     .fetch_optional::<(i64, String, i64)>()?;
+    /// assert_eq!(row, Some((420, "Control".into(), 420)));
+    ///
+    /// let row = rhosql::query("select :id, @name, :id", &mut db)
+    ///     .bind(420)
+    ///     .bind("Control")
+    ///     .fetch_optional::<(i64, String, i64)>()?;
+    /// assert_eq!(row, Some((420, "Control".into(), 420)));
+    ///
+    /// let err = rhosql::query("select ?, :name", &mut db).bind(420).fetch_optional::<(i64, i64)>();
+    /// assert!(matches!(err, Err(rhosql::Error::Bind(_))));
+    ///
+    /// let err = rhosql::query("select :a, ?", &mut db)
+    ///     .bind_named(":a", 1)
+    ///     .bind_named(":a", 2)
+    ///     .fetch_optional::<(i64, Option<i64>)>();
+    /// assert!(matches!(err, Err(rhosql::Error::Bind(BindError::DuplicateParameter(1)))));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn bind<V: Encode<'a>>(self, value: V) -> Self {
+        self.push_param(None, value)
+    }
+
+    /// Bind a parameter by name.
+    ///
+    /// The name should include its prefix, `:name`, `@name` or `$name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// # rhosql::query("create table post(name,content)", &mut db).execute()?;
+    /// rhosql::query("insert into post(name,content) values(:name,?)", &mut db)
+    ///     .bind("Content")
+    ///     .bind_named(":name", "Control")
+    ///     .execute()?;
+    ///
+    /// let post = rhosql::query("select name,content from post", &mut db)
+    ///     .fetch_optional::<(String, String)>()?;
+    /// assert_eq!(post, Some(("Control".into(), "Content".into())));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn bind_named<V: Encode<'a>>(self, name: &'a str, value: V) -> Self {
+        self.push_param(Some(name), value)
+    }
+
+    /// Bind multiple parameters at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// # rhosql::query("create table post(name,content)", &mut db).execute()?;
+    /// #[derive(rhosql::Bind)]
+    /// #[sql(by_name)]
+    /// struct NewPost {
+    ///     name: String,
+    ///     #[sql(rename = "body")]
+    ///     content: String,
+    ///     #[sql(skip)]
+    ///     draft: bool,
+    /// }
+    ///
+    /// let post = NewPost { name: "Control".into(), content: "Content".into(), draft: false };
+    ///
+    /// rhosql::query("insert into post(name,content) values(:name,:body)", &mut db)
+    ///     .bind_all(&post)
+    ///     .execute()?;
+    ///
+    /// let post = rhosql::query("select name,content from post", &mut db)
+    ///     .fetch_optional::<(String, String)>()?;
+    /// assert_eq!(post, Some(("Control".into(), "Content".into())));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn bind_all<B: Bind<'a>>(self, value: B) -> Self {
+        value.bind_all(self)
+    }
+
+    /// Bind multiple parameters at once, where parameter names are prefixed with given prefix.
+    ///
+    /// The prefix is inserted after the leading `:`, `@` or `$`, `:name` is bound to `:{prefix}name`,
+    /// and appended to the existing prefix when nested. Positional parameters are not affected.
+    ///
+    /// Used by `#[derive(Bind)]` for `#[sql(flatten, prefix = "..")]` field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// #[derive(rhosql::Bind)]
+    /// #[sql(by_name)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// let user = User { name: "Control".into() };
     ///
-    /// Note that parameter binding have hard limit of 16.
-    pub fn bind<V: Into<ValueRef<'a>>>(mut self, value: V) -> Self {
-        self.params.push(value.into());
+    /// let row = rhosql::query("select :author_name", &mut db)
+    ///     .bind_all_prefixed("author_", &user)
+    ///     .fetch_optional::<(String,)>()?;
+    /// assert_eq!(row, Some(("Control".into(),)));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn bind_all_prefixed<B: Bind<'a>>(mut self, prefix: &str, value: B) -> Self {
+        let len = self.prefix.len();
+        self.prefix.push_str(prefix);
+        let mut query = value.bind_all(self);
+        query.prefix.truncate(len);
+        query
+    }
+
+    /// Enable type coercion when decoding returned rows.
+    ///
+    /// See [`Row::coerce`] for more details.
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// let row = rhosql::query("select '420', 69", &mut db)
+    ///     .coerce()
+    ///     .fetch_optional::<(i64, f64)>()?;
+    /// assert_eq!(row, Some((420, 69.0)));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn coerce(mut self) -> Self {
+        self.coerce = true;
+        self
+    }
+
+    fn push_param<V: Encode<'a>>(mut self, name: Option<&'a str>, value: V) -> Self {
+        let name = name.map(|name| match name.split_at_checked(1) {
+            Some((sigil, name)) if !self.prefix.is_empty() => Cow::Owned(format!("{sigil}{}{name}", self.prefix)),
+            _ => Cow::Borrowed(name),
+        });
+        match value.encode() {
+            Ok(value) => self.params.push(Param { name, value }),
+            Err(err) => {
+                self.error.get_or_insert(err);
+            }
+        }
         self
     }
 }
 
+/// Result of [`Query::execute`].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// # let mut db = Connection::open_in_memory()?;
+/// # rhosql::query("create table post(name)", &mut db).execute()?;
+/// let result = rhosql::query("insert into post(name) values('Control'),('Vanish')", &mut db).execute()?;
+/// assert_eq!(result.rows_affected, 2);
+/// assert!(result.is_insert_statement);
+///
+/// let result = rhosql::query("delete from post where name = 'Vanish'", &mut db).execute()?;
+/// assert_eq!(result.rows_affected, 1);
+/// assert!(!result.is_insert_statement);
+///
+/// let result = rhosql::query("insert into post(rowid, name) values(0, 'Zero')", &mut db).execute()?;
+/// assert_eq!(result.last_insert_rowid, 0);
+/// assert!(result.is_insert_statement);
+///
+/// let result = rhosql::query("insert or ignore into post(rowid, name) values(0, 'Zero')", &mut db).execute()?;
+/// assert_eq!(result.rows_affected, 0);
+/// assert!(result.is_insert_statement);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecuteResult {
+    /// The number of rows modified, inserted or deleted by the statement.
+    ///
+    /// Changes caused by triggers or foreign key actions are not counted.
+    pub rows_affected: u64,
+    /// The rowid of the most recent successful INSERT on the connection.
+    ///
+    /// If the statement did not insert a row, this is the value before the statement executed.
+    pub last_insert_rowid: i64,
+    /// Whether the statement is an INSERT or REPLACE statement, including one with WITH clause.
+    ///
+    /// Combined with [`rows_affected`][ExecuteResult::rows_affected], it tells whether the
+    /// statement inserted rows. Note that sqlite does not distinguish an upsert which updated
+    /// the conflicting row, which is also counted in `rows_affected`.
+    pub is_insert_statement: bool,
+}
+
+/// Bind all parameters to the statement.
+///
+/// Returns error if a parameter is bound twice, or any parameter is left unbound.
+fn bind_params<S: Statement>(params: &[Param], stmt: S) -> Result<(), BindError> {
+    let count = stmt.bind_parameter_count();
+
+    // `?NNN` leave unused index nameless, which is indistinguishable from anonymous `?`,
+    // so nameless index is considered unused if any `?NNN` present
+    let numbered = (1..=count).any(|idx| stmt.bind_parameter_name(idx).is_some_and(|name| name.starts_with('?')));
+    let used = |idx: &i32| !numbered || stmt.bind_parameter_name(*idx).is_some();
+
+    let mut bound = BoundSet::new(count);
+
+    // named parameters first, so positional parameters take the remaining indices
+    for param in params {
+        let Some(name) = param.name.as_deref() else {
+            continue;
+        };
+        let idx = stmt.bind_parameter_index(&name.to_nul_string().map_err(StringError::from)?);
+        if idx == 0 {
+            return Err(BindError::UnknownName(name.into()));
+        }
+        if !bound.insert(idx) {
+            return Err(BindError::DuplicateParameter(idx));
+        }
+        param.value.bind(idx, &stmt)?;
+    }
+
+    let mut positions = (1..=count).filter(used);
+
+    for param in params.iter().filter(|param| param.name.is_none()) {
+        let Some(idx) = positions.find(|&idx| !bound.contains(idx)) else {
+            let expect = (1..=count).filter(used).count();
+            return Err(BindError::ParameterCount { expect, found: params.len() });
+        };
+        bound.insert(idx);
+        param.value.bind(idx, &stmt)?;
+    }
+
+    if (1..=count).filter(used).any(|idx| !bound.contains(idx)) {
+        let expect = (1..=count).filter(used).count();
+        return Err(BindError::ParameterCount { expect, found: params.len() });
+    }
+
+    Ok(())
+}
+
+/// Bitset of bound parameter index, which is one based.
+struct BoundSet(Vec<u64>);
+
+impl BoundSet {
+    fn new(count: i32) -> Self {
+        Self(vec![0; (count.max(0) as usize).div_ceil(64)])
+    }
+
+    fn contains(&self, idx: i32) -> bool {
+        let idx = (idx - 1) as usize;
+        self.0[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// returns `false` if the index is already bound
+    fn insert(&mut self, idx: i32) -> bool {
+        let contains = self.contains(idx);
+        let idx = (idx - 1) as usize;
+        self.0[idx / 64] |= 1 << (idx % 64);
+        !contains
+    }
+}
+
 impl<'s, S, E> Query<'_, S, E>
 where
     S: SqliteStr,
     E: Execute<'s>
 {
-    /// Collect result rows to a vector.
-    pub fn fetch_all<R: FromRow>(self) -> Result<Vec<R>> {
+    /// Prepare the statement and bind all parameters.
+    fn prepare(self) -> Result<StatementRef<'s>> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
         let stmt = self.db.prepare(self.sql)?;
 
-        for (param,idx) in self.params.into_iter().zip(1i32..) {
-            param.bind(idx, &stmt)?;
+        // cached statement is not reset when previous execution returns early on error,
+        // the returned error is from the previous execution, so it is ignored
+        let _ = stmt.reset();
+
+        // clear partially bound parameters, so cached statement does not keep them
+        if let Err(err) = bind_params(&self.params, &stmt) {
+            let _ = stmt.clear_bindings();
+            return Err(err.into());
         }
 
+        Ok(stmt)
+    }
+
+    /// Collect result rows to a vector.
+    pub fn fetch_all<R: for<'r> FromRow<'r>>(self) -> Result<Vec<R>> {
+        let coerce = self.coerce;
+        let stmt = self.prepare()?;
+
         let mut rows = vec![];
+        let state = RowState::default();
 
         while stmt.step()?.is_row() {
-            let row = Row::new(stmt.as_stmt_ptr());
+            let row = Row::new(stmt.as_stmt_ptr(), &state);
+            let row = if coerce { row.coerce() } else { row };
             rows.push(R::from_row(row)?);
         }
 
@@ -111,16 +435,15 @@ where
     }
 
     /// Optionally retrieve one row.
-    pub fn fetch_optional<R: FromRow>(self) -> Result<Option<R>> {
-        let stmt = self.db.prepare(self.sql)?;
-
-        for (param,idx) in self.params.into_iter().zip(1i32..) {
-            param.bind(idx, &stmt)?;
-        }
+    pub fn fetch_optional<R: for<'r> FromRow<'r>>(self) -> Result<Option<R>> {
+        let coerce = self.coerce;
+        let stmt = self.prepare()?;
+        let state = RowState::default();
 
         let row = match stmt.step()? {
             StepResult::Row => {
-                let row = Row::new(stmt.as_stmt_ptr());
+                let row = Row::new(stmt.as_stmt_ptr(), &state);
+                let row = if coerce { row.coerce() } else { row };
                 Ok(Some(R::from_row(row)?))
             }
             StepResult::Done => Ok(None),
@@ -132,31 +455,55 @@ where
         row
     }
 
+    /// Collect result rows to a vector via [`Deserialize`][serde::Deserialize].
+    ///
+    /// See [`de`][crate::de] for more details.
+    #[cfg(feature = "serde")]
+    pub fn fetch_all_de<T: serde::de::DeserializeOwned>(self) -> Result<Vec<T>> {
+        let rows = self.fetch_all::<crate::de::Deserialized<T>>()?;
+        Ok(rows.into_iter().map(|row| row.0).collect())
+    }
+
+    /// Optionally retrieve one row via [`Deserialize`][serde::Deserialize].
+    ///
+    /// See [`de`][crate::de] for more details.
+    #[cfg(feature = "serde")]
+    pub fn fetch_optional_de<T: serde::de::DeserializeOwned>(self) -> Result<Option<T>> {
+        let row = self.fetch_optional::<crate::de::Deserialized<T>>()?;
+        Ok(row.map(|row| row.0))
+    }
+
     /// Retrieve row by [`Iterator`]
     pub fn fetch(self) -> Result<RowStream<'s>> {
-        let stmt = self.db.prepare(self.sql)?;
-
-        for (param,idx) in self.params.into_iter().zip(1i32..) {
-            param.bind(idx, &stmt)?;
-        }
+        let coerce = self.coerce;
+        let stmt = self.prepare()?;
 
-        Ok(RowStream::new(stmt.as_stmt_ptr()))
+        Ok(RowStream::new(stmt.as_stmt_ptr(), coerce))
     }
 
-    /// Execute statement and return value of `last_insert_rowid`.
-    pub fn execute(self) -> Result<i64> {
-        let stmt = self.db.prepare(self.sql)?;
+    /// Execute statement and return the number of affected rows and `last_insert_rowid`.
+    ///
+    /// See [`ExecuteResult`] for more details.
+    pub fn execute(self) -> Result<ExecuteResult> {
+        let stmt = self.prepare()?;
         let db = stmt.as_db_ptr();
 
-        for (param,idx) in self.params.into_iter().zip(1i32..) {
-            param.bind(idx, &stmt)?;
-        }
+        let total_changes = db.total_changes();
+        let step = stmt.step();
+        let rows_affected = match db.total_changes() == total_changes {
+            true => 0,
+            false => db.changes(),
+        };
 
-        stmt.step()?;
+        step?;
         stmt.clear_bindings()?;
         stmt.reset()?;
 
-        Ok(db.last_insert_rowid())
+        Ok(ExecuteResult {
+            rows_affected: rows_affected as u64,
+            last_insert_rowid: db.last_insert_rowid(),
+            is_insert_statement: stmt.sql().is_some_and(|sql| is_insert(sql.as_bytes())),
+        })
     }
 }
 
@@ -1,48 +1,275 @@
-use std::marker::PhantomData;
+use std::{
+    borrow::Cow,
+    cell::{OnceCell, RefCell},
+    marker::PhantomData,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64,
+    },
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
-    Result,
+    Columns, Error, Result, Value,
     sqlite::{
         DataType, Statement, StatementExt,
         error::{BindError, DecodeError},
     },
 };
 
+/// Statement state shared by rows of the same statement.
+#[derive(Debug, Default)]
+pub(crate) struct RowState {
+    /// column datatypes recorded before the first in place conversion, cleared for each row
+    types: RefCell<Vec<DataType>>,
+    /// column names used in name lookup, collected on the first lookup
+    names: OnceCell<Box<[Box<str>]>>,
+}
+
 /// Row buffer.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Row<'row> {
     handle: *mut libsqlite3_sys::sqlite3_stmt,
     col_count: i32,
+    /// prefix of column name used in name lookup
+    prefix: String,
+    /// convert mismatched datatype following sqlite conversion rules
+    coerce: bool,
+    /// state shared by the statement
+    state: &'row RowState,
     _p: PhantomData<&'row mut ()>,
 }
 
-impl Row<'_> {
-    pub fn new(handle: *mut libsqlite3_sys::sqlite3_stmt) -> Self {
+impl<'r> Row<'r> {
+    /// create row of a statement that has just been stepped
+    ///
+    /// caller must ensure the statement is not stepped, reset or finalized for `'r`,
+    /// values returned from [`try_column`][Row::try_column] borrow from the statement for `'r`
+    ///
+    /// `state` should be shared by all rows of the same statement run
+    pub(crate) fn new(handle: *mut libsqlite3_sys::sqlite3_stmt, state: &'r RowState) -> Self {
+        state.types.borrow_mut().clear();
         Self {
             col_count: handle.data_count(),
             handle,
+            prefix: String::new(),
+            coerce: false,
+            state,
             _p: PhantomData,
         }
     }
 
+    /// enable type coercion for decoding
+    ///
+    /// by default, decoding requires the stored datatype to match the requested type, with
+    /// coercion, mismatched value is converted following [sqlite conversion rules][rules],
+    /// the same conversion performed by `sqlite3_column_*()`
+    ///
+    /// `NULL` is never coerced, use `Option` to decode nullable column
+    ///
+    /// sqlite converts INTEGER or FLOAT into TEXT or BLOB in place, the original datatype is
+    /// recorded before the conversion, so the column still decodes as its original datatype
+    /// afterward, BLOB and TEXT are converted into each other without sqlite, so no previously
+    /// decoded value is invalidated
+    ///
+    /// [rules]: https://www.sqlite.org/c3ref/column_blob.html
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// let mut rows = rhosql::query("select 420, '69', 4.2", &mut db).fetch()?;
+    /// let row = rows.next()?.unwrap();
+    /// assert!(row.try_decode::<f64>(0).is_err());
+    ///
+    /// let row = row.coerce();
+    /// assert_eq!(row.try_decode::<f64>(0)?, 420.0);
+    /// assert_eq!(row.try_decode::<i64>(1)?, 69);
+    /// assert_eq!(row.try_decode::<i64>(2)?, 4);
+    /// assert_eq!(row.try_decode::<String>(0)?, "420");
+    /// assert_eq!(row.try_column(0)?.data_type(), rhosql::sqlite::DataType::Int);
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn coerce(mut self) -> Self {
+        self.coerce = true;
+        self
+    }
+
+    /// returns `true` if type coercion is enabled
+    pub fn is_coerce(&self) -> bool {
+        self.coerce
+    }
+
+    /// returns a copy of current row where column name lookup is prefixed with given prefix
+    ///
+    /// prefix is appended to the existing prefix, useful for decoding joined columns
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        let mut row = self.clone();
+        row.prefix.push_str(prefix);
+        row
+    }
+
     /// try get single column from given index
-    pub fn try_column(&self, idx: i32) -> Result<ValueRef, DecodeError> {
+    ///
+    /// the value borrow from the statement, so it is valid as long as the row
+    pub fn try_column(&self, idx: i32) -> Result<ValueRef<'r>, DecodeError> {
         if idx >= self.col_count {
             return Err(DecodeError::IndexOutOfBounds);
         }
-        ValueRef::decode(idx, &self.handle)
+        let value = match self.state.types.borrow().get(idx as usize) {
+            Some(&datatype) => ValueRef::decode_as(idx, datatype, &self.handle)?,
+            None => ValueRef::decode(idx, &self.handle)?,
+        };
+        // SAFETY: the value is valid until the statement is stepped, reset or finalized,
+        // which `Row::new` caller guarantee cannot happen for `'r`
+        Ok(unsafe { std::mem::transmute::<ValueRef<'_>, ValueRef<'r>>(value) })
     }
 
     /// try decode single column from given index
-    pub fn try_decode<'a, D: Decode<'a>>(&'a self, idx: i32) -> Result<D> {
-        self.try_column(idx)?.try_decode()
+    ///
+    /// if coercion is enabled, mismatched datatype is converted and decoded once more
+    pub fn try_decode<D: Decode<'r>>(&self, idx: i32) -> Result<D> {
+        let value = self.try_column(idx)?;
+        match value.try_decode() {
+            Err(Error::Decode(DecodeError::InvalidDataType { expect, found }))
+                if self.coerce && found != DataType::Null =>
+            {
+                self.coerce_column(idx, value, expect)?.try_decode()
+            }
+            result => result,
+        }
+    }
+
+    /// convert column value into given datatype
+    fn coerce_column(&self, idx: i32, value: ValueRef<'r>, expect: DataType) -> Result<ValueRef<'r>, DecodeError> {
+        let value = match (expect, value) {
+            (DataType::Null, _) => value,
+            (DataType::Int, _) => ValueRef::Int(self.handle.column_int64(idx)),
+            (DataType::Float, _) => ValueRef::Float(self.handle.column_double(idx)),
+            // `sqlite3_column_text()` may reallocate the blob to add nul terminator,
+            // which invalidate previously decoded blob
+            (DataType::Text, ValueRef::Blob(blob)) => {
+                ValueRef::Text(std::str::from_utf8(blob).map_err(DecodeError::Utf8)?)
+            }
+            (DataType::Text, _) => {
+                // after the conversion, `sqlite3_column_type()` is undefined
+                self.record_types();
+                let text = self.handle.column_text(idx)?;
+                // SAFETY: see `try_column`
+                ValueRef::Text(unsafe { std::mem::transmute::<&str, &'r str>(text) })
+            }
+            (DataType::Blob, ValueRef::Text(text)) => ValueRef::Blob(text.as_bytes()),
+            (DataType::Blob, _) => {
+                self.record_types();
+                let blob = self.handle.column_blob(idx);
+                // SAFETY: see `try_column`
+                ValueRef::Blob(unsafe { std::mem::transmute::<&[u8], &'r [u8]>(blob) })
+            }
+        };
+        Ok(value)
+    }
+
+    /// record all column datatypes if not yet recorded for current row
+    fn record_types(&self) {
+        let mut types = self.state.types.borrow_mut();
+        if types.is_empty() {
+            types.extend((0..self.col_count).map(|idx| self.handle.column_type(idx)));
+        }
+    }
+
+    /// try decode single column by the column name
+    ///
+    /// if multiple columns have the same name, the first one is used
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// #[derive(Debug, rhosql::FromRow)]
+    /// #[sql(by_name)]
+    /// struct Post {
+    ///     id: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// let mut rows = rhosql::query("select 'Control' as name, 420 as id", &mut db).fetch()?;
+    /// let row = rows.next()?.unwrap();
+    /// assert_eq!(row.try_get::<i64>("id")?, 420);
+    ///
+    /// let post = row.try_row::<Post>()?;
+    /// assert_eq!(post.name, "Control");
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn try_get<D: Decode<'r>>(&self, name: &str) -> Result<D> {
+        match self.column_index(name) {
+            Some(idx) => self.try_decode(idx),
+            None => Err(DecodeError::ColumnNotFound(format!("{}{name}", self.prefix)).into()),
+        }
+    }
+
+    /// try decode single column by the column name, returns `None` if column not found or `NULL`
+    pub fn try_get_optional<D: Decode<'r>>(&self, name: &str) -> Result<Option<D>> {
+        match self.column_index(name) {
+            Some(idx) => self.try_decode(idx),
+            None => Ok(None),
+        }
+    }
+
+    /// try decode single column from given index, returns `None` if index out of bounds or `NULL`
+    pub fn try_decode_optional<D: Decode<'r>>(&self, idx: i32) -> Result<Option<D>> {
+        match idx < self.col_count {
+            true => self.try_decode(idx),
+            false => Ok(None),
+        }
+    }
+
+    /// return the index of the first column with given name
+    ///
+    /// the prefix from [`with_prefix`][Row::with_prefix] is included in the lookup, names are
+    /// compared case insensitively for ASCII characters, as sqlite does
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// let mut rows = rhosql::query("select 420 as Id, 'Control' as U_Name", &mut db).fetch()?;
+    /// let row = rows.next()?.unwrap();
+    /// assert_eq!(row.column_index("id"), Some(0));
+    /// assert_eq!(row.with_prefix("u_").column_index("NAME"), Some(1));
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn column_index(&self, name: &str) -> Option<i32> {
+        let names = self.state.names.get_or_init(|| {
+            (0..self.col_count).map(|idx| self.column_name(idx).unwrap_or_default().into()).collect()
+        });
+        let prefix = self.prefix.as_str();
+        let idx = names.iter().position(|col| {
+            col.split_at_checked(prefix.len())
+                .is_some_and(|(col_prefix, col)| col_prefix.eq_ignore_ascii_case(prefix) && col.eq_ignore_ascii_case(name))
+        })?;
+        Some(idx as i32)
     }
 
     /// try decode current row
-    pub fn try_row<D: FromRow>(self) -> Result<D> {
+    pub fn try_row<D: FromRow<'r>>(self) -> Result<D> {
         D::from_row(self)
     }
 
+    /// return the name of column at given index
+    pub fn column_name(&self, idx: i32) -> Option<&str> {
+        self.handle.column_name(idx)
+    }
+
+    /// returns the metadata of columns
+    pub fn columns(&self) -> Columns<'_> {
+        Columns::new(&self.handle)
+    }
+
     /// return the column count
     pub fn len(&self) -> usize {
         self.col_count as _
@@ -53,13 +280,21 @@ impl Row<'_> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// returns error if the column count is not equal to given count
+    fn check_len(&self, expect: usize) -> Result<(), DecodeError> {
+        match self.len() == expect {
+            true => Ok(()),
+            false => Err(DecodeError::ColumnCount { expect, found: self.len() }),
+        }
+    }
 }
 
 /// A borrowed sqlite value.
 #[derive(Debug)]
 pub enum ValueRef<'a> {
     Null,
-    Int(i32),
+    Int(i64),
     Float(f64),
     Text(&'a str),
     Blob(&'a [u8]),
@@ -72,7 +307,7 @@ impl ValueRef<'_> {
     pub fn bind<S: Statement>(&self, idx: i32, handle: S) -> Result<(), BindError> {
         match *self {
             ValueRef::Null => handle.bind_null(idx)?,
-            ValueRef::Int(int) => handle.bind_int(idx, int)?,
+            ValueRef::Int(int) => handle.bind_int64(idx, int)?,
             ValueRef::Float(fl) => handle.bind_double(idx, fl)?,
             ValueRef::Text(t) => handle.bind_text(idx, t)?,
             ValueRef::Blob(b) => handle.bind_blob(idx, b)?,
@@ -81,9 +316,14 @@ impl ValueRef<'_> {
     }
 
     pub fn decode<S: Statement>(idx: i32, handle: &S) -> Result<ValueRef<'_>, DecodeError> {
-        let value = match handle.column_type(idx) {
+        Self::decode_as(idx, handle.column_type(idx), handle)
+    }
+
+    /// decode column as given datatype, which should be the column datatype
+    fn decode_as<S: Statement>(idx: i32, datatype: DataType, handle: &S) -> Result<ValueRef<'_>, DecodeError> {
+        let value = match datatype {
             DataType::Null => ValueRef::Null,
-            DataType::Int => ValueRef::Int(handle.column_int(idx)),
+            DataType::Int => ValueRef::Int(handle.column_int64(idx)),
             DataType::Float => ValueRef::Float(handle.column_double(idx)),
             DataType::Text => ValueRef::Text(handle.column_text(idx)?),
             DataType::Blob => ValueRef::Blob(handle.column_blob(idx)),
@@ -124,6 +364,12 @@ impl From<()> for ValueRef<'_> {
 
 impl From<i32> for ValueRef<'_> {
     fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i64> for ValueRef<'_> {
+    fn from(value: i64) -> Self {
         Self::Int(value)
     }
 }
@@ -177,7 +423,109 @@ macro_rules! decode {
     };
 }
 
+macro_rules! decode_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Decode<'_> for $ty {
+                fn decode(value: ValueRef) -> Result<Self> {
+                    let int = i64::decode(value)?;
+                    <$ty>::try_from(int).map_err(|_| {
+                        DecodeError::Overflow { value: int, ty: stringify!($ty) }.into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
 /// A type that can be construced from sqlite value.
+///
+/// Newtypes and unit enums can derive it via `#[derive(Decode)]`, along with [`Encode`][crate::Encode].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// #[derive(Debug, PartialEq, rhosql::Decode, rhosql::Encode)]
+/// struct UserId(i64);
+///
+/// #[derive(Debug, PartialEq, rhosql::Decode, rhosql::Encode)]
+/// #[sql(rename_all = "snake_case")]
+/// enum Status {
+///     Active,
+///     OnHold,
+/// }
+///
+/// #[derive(Debug, PartialEq, rhosql::Decode, rhosql::Encode)]
+/// #[repr(u8)]
+/// enum Level {
+///     Low = 1,
+///     High = 2,
+/// }
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+///
+/// let row = rhosql::query("select ?1, ?2, ?3, typeof(?2), typeof(?3)", &mut db)
+///     .bind(UserId(420))
+///     .bind(Status::OnHold)
+///     .bind(Level::High)
+///     .fetch_optional::<(UserId, Status, Level, String, String)>()?
+///     .unwrap();
+///
+/// assert_eq!(row, (UserId(420), Status::OnHold, Level::High, "text".into(), "integer".into()));
+///
+/// let err = rhosql::query("select 'archived'", &mut db).fetch_all::<(Status,)>();
+/// assert!(err.unwrap_err().to_string().contains("unknown variant `archived` for `Status`"));
+/// #   Ok(())
+/// # }
+/// ```
+///
+/// # Storage
+///
+/// Standard library types are stored as follows, value that does not fit the type returns
+/// error instead of being truncated:
+///
+/// | Type | Storage |
+/// | ---- | ------- |
+/// | integers and `NonZero*` | INTEGER, checked for range and zero |
+/// | `bool` | INTEGER, strictly `0` or `1` |
+/// | `f32`, `f64` | REAL, finite `f64` outside of `f32` range is error |
+/// | `char` | TEXT with exactly one character |
+/// | `String`, `&str`, `Box<str>`, `Arc<str>`, `Cow<str>` | TEXT |
+/// | `Vec<u8>`, `&[u8]` | BLOB |
+/// | `[u8; N]` | BLOB with exactly `N` bytes |
+/// | [`Duration`] | INTEGER nanoseconds |
+/// | [`SystemTime`] | INTEGER unix timestamp in nanoseconds, can be negative |
+/// | [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`] | TEXT |
+/// | [`PathBuf`], `&Path` | TEXT, encoding non UTF-8 path is error |
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use std::{net::IpAddr, num::NonZeroU32, time::{Duration, SystemTime}};
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+/// let now = SystemTime::now();
+/// let ip: IpAddr = "127.0.0.1".parse().unwrap();
+///
+/// let row = rhosql::query("select ?1, ?2, ?3, ?4, ?5", &mut db)
+///     .bind(Duration::from_millis(1500))
+///     .bind(now)
+///     .bind(ip)
+///     .bind([0xde, 0xad, 0xbe, 0xef])
+///     .bind(true)
+///     .fetch_optional::<(i64, SystemTime, IpAddr, [u8; 4], bool)>()?
+///     .unwrap();
+///
+/// assert_eq!(row, (1_500_000_000, now, ip, [0xde, 0xad, 0xbe, 0xef], true));
+///
+/// let err = rhosql::query("select 0", &mut db).fetch_optional::<NonZeroU32>().unwrap_err();
+/// assert!(err.to_string().contains("value `0` is not a valid `NonZeroU32`"));
+///
+/// let err = rhosql::query("select x'dead'", &mut db).fetch_optional::<[u8; 4]>().unwrap_err();
+/// assert!(err.to_string().contains("blob length missmatch, expect `4` found `2`"));
+/// #   Ok(())
+/// # }
+/// ```
 pub trait Decode<'a>: Sized {
     fn decode(value: ValueRef<'a>) -> Result<Self>;
 }
@@ -208,27 +556,224 @@ impl<'a> Decode<'a> for &'a [u8] {
     }
 }
 
+/// Nullable column, `NULL` is decoded as `None`.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// # let mut db = Connection::open_in_memory()?;
+/// #[derive(Debug, rhosql::FromRow)]
+/// struct Post {
+///     name: String,
+///     content: Option<String>,
+/// }
+///
+/// # rhosql::query("create table post(name,content)", &mut db).execute()?;
+/// rhosql::query("insert into post(name,content) values(?1,?2)", &mut db)
+///     .bind("Control")
+///     .bind(None::<&str>)
+///     .execute()?;
+///
+/// let post = rhosql::query("select name,content from post", &mut db).fetch_optional::<Post>()?.unwrap();
+/// assert_eq!(post.content, None);
+/// #   Ok(())
+/// # }
+/// ```
+impl<'a, D: Decode<'a>> Decode<'a> for Option<D> {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        match value {
+            ValueRef::Null => Ok(None),
+            _ => D::decode(value).map(Some),
+        }
+    }
+}
+
 decode!((), DataType::Null, ValueRef::Null => ());
-decode!(i32, DataType::Int, ValueRef::Int(i) => i);
+decode!(i64, DataType::Int, ValueRef::Int(i) => i);
+decode_int!(i8, i16, i32, u8, u16, u32, u64);
 decode!(f64, DataType::Float, ValueRef::Float(i) => i);
 decode!(&str as String);
+decode!(&str as Box<str>);
+decode!(&str as Arc<str>);
+decode!(&str as PathBuf);
 decode!(&[u8] as Vec<u8>);
 
+impl Decode<'_> for bool {
+    fn decode(value: ValueRef) -> Result<Self> {
+        match i64::decode(value)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            int => Err(DecodeError::InvalidFormat { ty: "bool", value: int.to_string() }.into()),
+        }
+    }
+}
+
+impl Decode<'_> for f32 {
+    fn decode(value: ValueRef) -> Result<Self> {
+        let float = f64::decode(value)?;
+        match float as f32 {
+            // infinity and nan is kept as is
+            float32 if float32.is_finite() || !float.is_finite() => Ok(float32),
+            _ => Err(DecodeError::InvalidFormat { ty: "f32", value: format!("{float:e}") }.into()),
+        }
+    }
+}
+
+impl Decode<'_> for char {
+    fn decode(value: ValueRef) -> Result<Self> {
+        let text = <&str>::decode(value)?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Ok(ch),
+            _ => Err(DecodeError::InvalidFormat { ty: "char", value: text.into() }.into()),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for Cow<'a, str> {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        <&str>::decode(value).map(Cow::Borrowed)
+    }
+}
+
+impl<'a> Decode<'a> for &'a Path {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        <&str>::decode(value).map(Path::new)
+    }
+}
+
+impl<'a, const N: usize> Decode<'a> for &'a [u8; N] {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        let blob = <&[u8]>::decode(value)?;
+        blob.try_into().map_err(|_| DecodeError::InvalidLength { expect: N, found: blob.len() }.into())
+    }
+}
+
+impl<const N: usize> Decode<'_> for [u8; N] {
+    fn decode(value: ValueRef) -> Result<Self> {
+        <&[u8; N]>::decode(value).copied()
+    }
+}
+
+macro_rules! decode_non_zero {
+    ($($ty:ident($int:ty)),*) => {
+        $(
+            impl Decode<'_> for $ty {
+                fn decode(value: ValueRef) -> Result<Self> {
+                    $ty::new(<$int>::decode(value)?).ok_or_else(|| {
+                        DecodeError::InvalidFormat { ty: stringify!($ty), value: "0".into() }.into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+decode_non_zero!(
+    NonZeroI8(i8), NonZeroI16(i16), NonZeroI32(i32), NonZeroI64(i64),
+    NonZeroU8(u8), NonZeroU16(u16), NonZeroU32(u32), NonZeroU64(u64)
+);
+
+macro_rules! decode_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl Decode<'_> for $ty {
+                fn decode(value: ValueRef) -> Result<Self> {
+                    let text = <&str>::decode(value)?;
+                    text.parse().map_err(|_| {
+                        DecodeError::InvalidFormat { ty: stringify!($ty), value: text.into() }.into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+decode_from_str!(IpAddr, Ipv4Addr, Ipv6Addr);
+
+impl Decode<'_> for Duration {
+    fn decode(value: ValueRef) -> Result<Self> {
+        let nanos = i64::decode(value)?;
+        match u64::try_from(nanos) {
+            Ok(nanos) => Ok(Duration::from_nanos(nanos)),
+            Err(_) => Err(DecodeError::Overflow { value: nanos, ty: "Duration" }.into()),
+        }
+    }
+}
+
+impl Decode<'_> for SystemTime {
+    fn decode(value: ValueRef) -> Result<Self> {
+        let nanos = i64::decode(value)?;
+        let time = match u64::try_from(nanos) {
+            Ok(after) => UNIX_EPOCH.checked_add(Duration::from_nanos(after)),
+            Err(_) => UNIX_EPOCH.checked_sub(Duration::from_nanos(nanos.unsigned_abs())),
+        };
+        time.ok_or_else(|| DecodeError::Overflow { value: nanos, ty: "SystemTime" }.into())
+    }
+}
+
 
 /// A type that can be construced from sqlite row.
-pub trait FromRow: Sized + std::fmt::Debug {
-    fn from_row(row: Row) -> Result<Self>;
+///
+/// The lifetime `'r` is the lifetime of the row, which allow decoding borrowed value like `&'r str`.
+/// Owned type which implement it for any lifetime can be collected via
+/// [`Query::fetch_all`][crate::query::Query::fetch_all].
+///
+/// Usually derived via `#[derive(FromRow)]`, which also support enum discriminated by a tag column.
+///
+/// Every [`Decode`] type is a single column `FromRow`, which requires the row to have exactly one
+/// column, and tuple of [`Decode`] types up to 16 elements is decoded by position, where extra
+/// columns are ignored. Because of that blanket implementation, a type that implement [`Decode`] cannot implement
+/// `FromRow`, e.g: deriving both `Decode` and `FromRow` is a conflicting implementation.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// #[derive(Debug, PartialEq, rhosql::FromRow)]
+/// #[sql(tag = "kind", rename_all = "snake_case")]
+/// enum Event {
+///     Login { user: String },
+///     Transfer { from: i64, to: i64 },
+///     Logout,
+/// }
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+/// db.execute_batch("
+///     create table event(kind, user, \"from\", \"to\");
+///     insert into event values('login', 'Control', null, null);
+///     insert into event values('transfer', null, 1, 2);
+///     insert into event values('logout', null, null, null);
+/// ")?;
+///
+/// let events = rhosql::query("select * from event", &mut db).fetch_all::<Event>()?;
+/// assert_eq!(events, [
+///     Event::Login { user: "Control".into() },
+///     Event::Transfer { from: 1, to: 2 },
+///     Event::Logout,
+/// ]);
+///
+/// rhosql::query("insert into event(kind) values('refund')", &mut db).execute()?;
+/// let err = rhosql::query("select * from event", &mut db).fetch_all::<Event>();
+/// assert!(err.unwrap_err().to_string().contains("unknown variant `refund` for `Event`"));
+/// #   Ok(())
+/// # }
+/// ```
+pub trait FromRow<'r>: Sized {
+    fn from_row(row: Row<'r>) -> Result<Self>;
 }
 
 macro_rules! from_tuple {
     ($($id:ident $i:literal),*) => {
-        impl<$($id),*> FromRow for ($($id),*,)
+        impl<'r, $($id),*> FromRow<'r> for ($($id),*,)
         where
-            $($id: for<'a> Decode<'a>+ std::fmt::Debug ),*
+            $($id: Decode<'r>),*
         {
-            fn from_row(row: Row) -> Result<Self> {
+            fn from_row(row: Row<'r>) -> Result<Self> {
                 Ok((
-                    $(row.try_column($i)?.try_decode::<$id>()?),*,
+                    $(row.try_decode::<$id>($i)?),*,
                 ))
             }
         }
@@ -242,4 +787,130 @@ from_tuple!(R1 0,R2 1,R3 2,R4 3);
 from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4);
 from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5);
 from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10,R12 11);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10,R12 11,R13 12);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10,R12 11,R13 12,R14 13);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10,R12 11,R13 12,R14 13,R15 14);
+from_tuple!(R1 0,R2 1,R3 2,R4 3,R5 4,R6 5,R7 6,R8 7,R9 8,R10 9,R11 10,R12 11,R13 12,R14 13,R15 14,R16 15);
+
+
+/// Single column row.
+///
+/// Returns error if the row does not have exactly one column.
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// # let mut db = Connection::open_in_memory()?;
+/// let names = rhosql::query("select 'Control' union all select 'Alan'", &mut db).fetch_all::<String>()?;
+/// assert_eq!(names, ["Control", "Alan"]);
+///
+/// let count = rhosql::query("select count(*) from pragma_table_list", &mut db).fetch_optional::<i64>()?;
+/// assert!(count.is_some());
+///
+/// let err = rhosql::query("select 'Control', 420", &mut db).fetch_optional::<String>();
+/// # use rhosql::sqlite::error::DecodeError;
+/// assert!(matches!(err, Err(rhosql::Error::Decode(DecodeError::ColumnCount { expect: 1, found: 2 }))));
+/// #   Ok(())
+/// # }
+/// ```
+impl<'r, T: Decode<'r>> FromRow<'r> for T {
+    fn from_row(row: Row<'r>) -> Result<Self> {
+        row.check_len(1)?;
+        row.try_decode(0)
+    }
+}
 
+impl FromRow<'_> for Vec<Value> {
+    fn from_row(row: Row) -> Result<Self> {
+        (0..row.col_count)
+            .map(|idx| Ok(row.try_column(idx)?.into()))
+            .collect()
+    }
+}
+
+/// A row with its column names, decoded into owned values.
+///
+/// Useful for query which its result columns is unknown at compile time.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::{Connection, DynamicRow, Value};
+/// # let mut db = Connection::open_in_memory()?;
+/// let rows = rhosql::query("select 420 as id, 'Control' as name", &mut db).fetch_all::<DynamicRow>()?;
+///
+/// assert_eq!(rows[0].columns(), ["id", "name"]);
+/// assert_eq!(rows[0].get("name"), Some(&Value::Text("Control".into())));
+/// assert_eq!(rows[0].get("ID"), Some(&Value::Int(420)));
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicRow {
+    columns: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl DynamicRow {
+    /// Returns the column names.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the values.
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Returns value of the first column with given name, compared case insensitively for
+    /// ASCII characters, as sqlite does.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        let idx = self.columns.iter().position(|col| col.eq_ignore_ascii_case(name))?;
+        self.values.get(idx)
+    }
+
+    /// Returns value at given index.
+    pub fn get_index(&self, idx: usize) -> Option<&Value> {
+        self.values.get(idx)
+    }
+
+    /// Returns an iterator of column name and value pair.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.columns.iter().map(String::as_str).zip(&self.values)
+    }
+
+    /// Returns the column count.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there is no column.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Consume the row and returns the values.
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+}
+
+impl FromRow<'_> for DynamicRow {
+    fn from_row(row: Row) -> Result<Self> {
+        let columns = (0..row.col_count)
+            .map(|idx| row.column_name(idx).unwrap_or_default().to_owned())
+            .collect();
+
+        Ok(Self {
+            columns,
+            values: Vec::from_row(row)?,
+        })
+    }
+}
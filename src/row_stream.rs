@@ -1,8 +1,8 @@
 use std::marker::PhantomData;
 
 use crate::{
-    FromRow, Result,
-    row::Row,
+    Columns, FromRow, Result,
+    row::{Row, RowState},
     sqlite::{StatementExt, error::StepError},
 };
 
@@ -11,20 +11,32 @@ use crate::{
 pub struct RowStream<'stmt> {
     handle: *mut libsqlite3_sys::sqlite3_stmt,
     done: bool,
+    /// enable type coercion for returned rows
+    coerce: bool,
+    /// state shared by returned rows
+    state: RowState,
     _p: PhantomData<&'stmt mut ()>,
 }
 
 impl RowStream<'_> {
     /// the statement parameter should already bound and ready to step.
-    pub(crate) fn new(handle: *mut libsqlite3_sys::sqlite3_stmt) -> Self {
+    pub(crate) fn new(handle: *mut libsqlite3_sys::sqlite3_stmt, coerce: bool) -> Self {
         Self {
             handle,
             done: false,
+            coerce,
+            state: RowState::default(),
             _p: PhantomData,
         }
     }
 
+    /// returns the metadata of result columns, available before the first row is fetched
+    pub fn columns(&self) -> Columns<'_> {
+        Columns::new(&self.handle)
+    }
+
     /// fetch the next row
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Option<Row<'_>>, StepError> {
         if self.done {
             return Ok(None);
@@ -35,15 +47,50 @@ impl RowStream<'_> {
             return Ok(None);
         }
 
-        Ok(Some(Row::new(self.handle)))
+        let row = Row::new(self.handle, &self.state);
+        Ok(Some(if self.coerce { row.coerce() } else { row }))
     }
 
-    pub fn next_row<R: FromRow>(&mut self) -> Result<Option<R>> {
+    /// fetch the next row and decode it
+    ///
+    /// the decoded value can borrow from the row, e.g: `&str`, so no copy is required
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// #[derive(Debug, rhosql::FromRow)]
+    /// struct PostRef<'a> {
+    ///     id: i64,
+    ///     name: &'a str,
+    /// }
+    ///
+    /// let mut rows = rhosql::query("select 420, 'Control'", &mut db).fetch()?;
+    ///
+    /// while let Some(post) = rows.next_row::<PostRef>()? {
+    ///     assert_eq!(post.id, 420);
+    ///     assert_eq!(post.name, "Control");
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn next_row<'r, R: FromRow<'r>>(&'r mut self) -> Result<Option<R>> {
         Ok(match self.next()? {
             Some(ok) => Some(R::from_row(ok)?),
             None => None,
         })
     }
+
+    /// fetch the next row and deserialize it via [`Deserialize`][serde::Deserialize]
+    ///
+    /// See [`de`][crate::de] for more details.
+    #[cfg(feature = "serde")]
+    pub fn next_row_de<'r, T: serde::Deserialize<'r>>(&'r mut self) -> Result<Option<T>> {
+        Ok(match self.next()? {
+            Some(ok) => Some(crate::de::from_row(ok)?),
+            None => None,
+        })
+    }
 }
 
 impl Drop for RowStream<'_> {
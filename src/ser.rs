@@ -0,0 +1,136 @@
+//! Integration with [`serde`] serialization, requires `serde` feature.
+//!
+//! [`Row`] and [`DynamicRow`] are serialized as a map of column name to its value, if
+//! multiple columns have the same name, the first one is used.
+//!
+//! Value is serialized as its corresponding primitive, `NULL` as unit, which is `null` in json,
+//! and BLOB as base64 string.
+//!
+//! Use [`RowStream::write_json`] to write all rows as json array without collecting them.
+//!
+//! # Example
+//!
+//! ```
+//! # fn main() -> rhosql::Result<()> {
+//! let mut db = rhosql::Connection::open_in_memory()?;
+//!
+//! let mut rows = rhosql::query("select 420 as id, 'Control' as name, null as content, x'deadbeef' as data", &mut db)
+//!     .fetch()?;
+//!
+//! let row = rows.next()?.unwrap();
+//! assert_eq!(
+//!     serde_json::to_string(&row).unwrap(),
+//!     r#"{"id":420,"name":"Control","content":null,"data":"3q2+7w=="}"#,
+//! );
+//! #   Ok(())
+//! # }
+//! ```
+use serde::ser::{Error, Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::{
+    fmt::{self, Write},
+    io,
+};
+
+use crate::{DynamicRow, Row, RowStream, Value, ValueRef};
+
+impl Serialize for Row<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        for idx in 0..self.len() as i32 {
+            let name = self.column_name(idx);
+            if (0..idx).any(|prev| self.column_name(prev) == name) {
+                continue;
+            }
+            let value = self.try_column(idx).map_err(S::Error::custom)?;
+            match name {
+                Some(name) => map.serialize_entry(name, &value)?,
+                None => map.serialize_entry(&idx, &value)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for DynamicRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (i, (name, value)) in self.iter().enumerate() {
+            if self.columns()[..i].iter().any(|prev| prev == name) {
+                continue;
+            }
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for ValueRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            ValueRef::Null => serializer.serialize_unit(),
+            ValueRef::Int(int) => serializer.serialize_i64(int),
+            ValueRef::Float(float) => serializer.serialize_f64(float),
+            ValueRef::Text(text) => serializer.serialize_str(text),
+            ValueRef::Blob(blob) => serializer.collect_str(&Base64(blob)),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl RowStream<'_> {
+    /// write all remaining rows as json array of objects
+    ///
+    /// rows are written as they are fetched, so no intermediate collection is required,
+    /// see [`ser`][crate::ser] for the format of each row
+    ///
+    /// ```
+    /// # fn main() -> rhosql::Result<()> {
+    /// # use rhosql::Connection;
+    /// # let mut db = Connection::open_in_memory()?;
+    /// let mut rows = rhosql::query("select 1 as id, 'Control' as name union all select 2, null", &mut db).fetch()?;
+    ///
+    /// let mut output = vec![];
+    /// rows.write_json(&mut output).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(output).unwrap(),
+    ///     r#"[{"id":1,"name":"Control"},{"id":2,"name":null}]"#,
+    /// );
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn write_json<W: io::Write>(&mut self, writer: W) -> io::Result<()> {
+        let mut serializer = serde_json::Serializer::new(writer);
+        let mut seq = serializer.serialize_seq(None)?;
+        while let Some(row) = self.next().map_err(io::Error::other)? {
+            seq.serialize_element(&row)?;
+        }
+        SerializeSeq::end(seq)?;
+        Ok(())
+    }
+}
+
+/// display bytes as standard base64 with padding
+struct Base64<'a>(&'a [u8]);
+
+impl fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        for chunk in self.0.chunks(3) {
+            let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - i * 8));
+            for i in 0..4 {
+                match i <= chunk.len() {
+                    true => f.write_char(TABLE[(n >> (18 - i * 6)) as usize & 0x3f] as char)?,
+                    false => f.write_char('=')?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 use libsqlite3_sys::{self as ffi};
 use std::{ffi::CStr, ptr, time::Duration};
 
-use super::{DatabaseError, OpenFlag, SqliteMutexGuard, error::ConfigureError};
+use super::{
+    DatabaseError, OpenFlag, SqliteMutexGuard,
+    error::{BatchError, ConfigureError},
+    statement::{blank_len, prepare_v2_tail},
+};
+use crate::SqliteStr;
 
 macro_rules! ffi_db {
     (@ $method:ident($db:expr $(, $($args:expr),*)?), $into:ty $(, $ret:expr)?) => {
@@ -91,6 +96,79 @@ pub trait DatabaseExt: Database {
         unsafe { ffi::sqlite3_last_insert_rowid(self.as_ptr()) }
     }
 
+    /// returns the number of rows modified, inserted or deleted by the most recently completed
+    /// INSERT, UPDATE or DELETE statement on database connection
+    ///
+    /// this is a wrapper for `sqlite3_changes64()` with `bundled` feature, which requires
+    /// sqlite 3.37.0, otherwise `sqlite3_changes()` which is limited to 32 bit
+    fn changes(&self) -> i64 {
+        #[cfg(feature = "bundled")]
+        unsafe { ffi::sqlite3_changes64(self.as_ptr()) }
+        #[cfg(not(feature = "bundled"))]
+        unsafe { ffi::sqlite3_changes(self.as_ptr()).into() }
+    }
+
+    /// returns the total number of rows inserted, modified or deleted by all INSERT, UPDATE or DELETE
+    /// statements completed since the database connection was opened
+    ///
+    /// this is a wrapper for `sqlite3_total_changes64()` with `bundled` feature, which requires
+    /// sqlite 3.37.0, otherwise `sqlite3_total_changes()` which is limited to 32 bit
+    fn total_changes(&self) -> i64 {
+        #[cfg(feature = "bundled")]
+        unsafe { ffi::sqlite3_total_changes64(self.as_ptr()) }
+        #[cfg(not(feature = "bundled"))]
+        unsafe { ffi::sqlite3_total_changes(self.as_ptr()).into() }
+    }
+
+    /// returns `false` if the given database connection is in the middle of a transaction
+    ///
+    /// autocommit mode is on by default, and disabled by a `BEGIN` statement
+    ///
+    /// this is a wrapper for `sqlite3_get_autocommit()`
+    fn get_autocommit(&self) -> bool {
+        unsafe { ffi::sqlite3_get_autocommit(self.as_ptr()) != 0 }
+    }
+
+    /// Execute sql which may contain multiple statements.
+    ///
+    /// Each statement is prepared, stepped until completion, and finalized in order,
+    /// whitespace and comments between statements are skipped.
+    ///
+    /// Returns the byte offset of the failed statement on error, previous statements
+    /// are not rolled back.
+    fn execute_batch<S: SqliteStr>(&self, sql: S) -> Result<(), BatchError> {
+        let db = self.as_ptr();
+        let (ptr, len, _) = sql.as_nulstr();
+        let sql = unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), len as usize) };
+        let mut offset = blank_len(sql);
+
+        while offset < sql.len() {
+            let (stmt, consumed) = prepare_v2_tail(db, &sql[offset..])
+                .map_err(|error| BatchError { offset, error })?;
+
+            if !stmt.is_null() {
+                let result = loop {
+                    match unsafe { ffi::sqlite3_step(stmt) } {
+                        ffi::SQLITE_ROW => continue,
+                        ffi::SQLITE_DONE => break Ok(()),
+                        code => break Err(DatabaseError::from_code(code, db)),
+                    }
+                };
+                unsafe { ffi::sqlite3_finalize(stmt) };
+                result.map_err(|error| BatchError { offset, error })?;
+            }
+
+            if consumed == 0 {
+                break;
+            }
+
+            offset += consumed;
+            offset += blank_len(&sql[offset..]);
+        }
+
+        Ok(())
+    }
+
     /// attempt to enter a mutex, if another thread is already within the mutex,
     /// this call will block
     ///
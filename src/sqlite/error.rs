@@ -109,9 +109,7 @@ macro_rules! opaque_error {
 }
 
 pub(crate) use display_error;
-pub(crate) use is_busy;
 pub(crate) use from;
-pub(crate) use opaque_error;
 
 /// An error returned from database.
 pub struct DatabaseError {
@@ -225,14 +223,89 @@ display_error! {
 }
 
 opaque_error!(ConfigureError, #failedto "configure database");
-opaque_error!(PrepareError, #failedto "create prepared statement");
 opaque_error!(StepError, #failedto "get the next row");
 opaque_error!(ResetError, #failedto "reset or clear binding prepared statement");
 
-/// An error when failed to decode value
+/// An error when failed to access blob
+pub enum BlobError {
+    /// An error when failed to convert rust to sqlite string
+    String(StringError),
+    /// An error returned from database
+    Database(DatabaseError),
+    /// Length or offset is out of range of sqlite blob api.
+    OutOfRange,
+}
+
+impl From<std::ffi::NulError> for BlobError {
+    fn from(value: std::ffi::NulError) -> Self {
+        Self::String(value.into())
+    }
+}
+
+from! {
+    BlobError,
+    for DatabaseError => Database,
+    for StringError => String
+}
+
+is_busy!(BlobError, me => matches!(me,BlobError::Database(d) if d.is_busy()));
+
+display_error! {
+    BlobError,
+    #prefix "Failed to access blob: ",
+    #delegate Database String,
+    Self::OutOfRange => ("length or offset is out of range"),
+}
+
+/// An error when failed to create prepared statement
+pub enum PrepareError {
+    /// An error returned from database
+    Database(DatabaseError),
+    /// Sql contains more than one statement.
+    TrailingSql {
+        /// Byte offset of the unconsumed sql.
+        offset: usize,
+    },
+}
+
+from!(PrepareError, for DatabaseError => Database);
+
+is_busy!(PrepareError, me => matches!(me,PrepareError::Database(d) if d.is_busy()));
+
+display_error! {
+    PrepareError,
+    #prefix "Failed to create prepared statement: ",
+    #delegate Database,
+    Self::TrailingSql { offset } => ("unconsumed sql at byte {offset}, multiple statements requires `execute_batch`"),
+}
+
+/// An error when failed to execute multiple statements
+pub struct BatchError {
+    /// Byte offset of the failed statement in the sql.
+    pub offset: usize,
+    /// An error returned from database.
+    pub error: DatabaseError,
+}
+
+display_error! {
+    BatchError,
+    me => write!("Failed to execute statement at byte {}: {}", me.offset, me.error)
+}
+
+/// An error when failed to bind value
 pub enum BindError {
     String(StringError),
     Database(DatabaseError),
+    /// No parameter with given name found in the statement.
+    UnknownName(String),
+    /// Bound parameter count does not match the statement parameter count.
+    ParameterCount { expect: usize, found: usize },
+    /// Parameter at given index is bound more than once.
+    DuplicateParameter(i32),
+    /// Value is out of range of what sqlite can store.
+    Overflow { ty: &'static str },
+    /// Custom error, e.g: failed serialization of user defined type.
+    Custom(String),
 }
 
 from! {
@@ -244,7 +317,12 @@ from! {
 display_error! {
     BindError,
     #prefix "Failed to bind value: ",
-    #delegate String Database
+    #delegate String Database,
+    Self::UnknownName(name) => ("no parameter named `{name}`"),
+    Self::ParameterCount { expect, found } => ("parameter count mismatch, expect `{expect}` found `{found}`"),
+    Self::DuplicateParameter(idx) => ("parameter at index `{idx}` is bound more than once"),
+    Self::Overflow { ty } => ("value of `{ty}` is out of range of sqlite"),
+    Self::Custom(msg) => ("{msg}"),
 }
 
 /// An error when failed to decode value
@@ -252,13 +330,47 @@ pub enum DecodeError {
     IndexOutOfBounds,
     InvalidDataType { expect: DataType, found: DataType },
     Utf8(Utf8Error),
+    /// Integer value is out of range of the requested type.
+    Overflow { value: i64, ty: &'static str },
+    /// No column with given name found in the row.
+    ColumnNotFound(String),
+    /// Value does not match any variant of the enum.
+    UnknownVariant { ty: &'static str, value: String },
+    /// Value is not in the expected format of the type.
+    InvalidFormat { ty: &'static str, value: String },
+    /// Blob length does not match the length of fixed size type.
+    InvalidLength { expect: usize, found: usize },
+    /// Row column count does not match the column count of the requested type.
+    ColumnCount { expect: usize, found: usize },
+    /// Custom error, e.g: failed conversion of user defined type.
+    Custom(String),
+    /// An error when decoding specific column.
+    Column { name: String, error: Box<DecodeError> },
 }
 
 display_error! {
     DecodeError,
-    #prefix "Failed to decode value: ",
-    #delegate Utf8,
-    Self::IndexOutOfBounds => ("row index out of bounds"),
-    Self::InvalidDataType { expect, found }=> ("datatype requested missmatch, expect `{expect}` found `{found}`"),
+    me => write!("Failed to decode value: {}", DecodeMessage(me))
+}
+
+/// display [`DecodeError`] without prefix, so nested error is not prefixed twice
+struct DecodeMessage<'a>(&'a DecodeError);
+
+impl std::fmt::Display for DecodeMessage<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            DecodeError::IndexOutOfBounds => write!(f, "row index out of bounds"),
+            DecodeError::InvalidDataType { expect, found }=> write!(f, "datatype requested missmatch, expect `{expect}` found `{found}`"),
+            DecodeError::Utf8(e) => std::fmt::Display::fmt(e, f),
+            DecodeError::Overflow { value, ty } => write!(f, "value `{value}` is out of range of `{ty}`"),
+            DecodeError::ColumnNotFound(name) => write!(f, "column `{name}` not found"),
+            DecodeError::UnknownVariant { ty, value } => write!(f, "unknown variant `{value}` for `{ty}`"),
+            DecodeError::InvalidFormat { ty, value } => write!(f, "value `{value}` is not a valid `{ty}`"),
+            DecodeError::InvalidLength { expect, found } => write!(f, "blob length missmatch, expect `{expect}` found `{found}`"),
+            DecodeError::ColumnCount { expect, found } => write!(f, "column count missmatch, expect `{expect}` found `{found}`"),
+            DecodeError::Custom(msg) => write!(f, "{msg}"),
+            DecodeError::Column { name, error } => write!(f, "column `{name}`, {}", DecodeMessage(error)),
+        }
+    }
 }
 
@@ -11,12 +11,13 @@ pub use error::DatabaseError;
 pub use open_flag::OpenFlag;
 pub use database::{Database, DatabaseExt};
 pub use statement::{Statement, StatementExt};
-pub use raii::{SqliteHandle, StatementHandle, SqliteMutexGuard};
+pub(crate) use statement::is_insert;
+pub use raii::{SqliteHandle, StatementHandle, SqliteMutexGuard, BlobHandle};
 
 macro_rules! flags {
     ($(#[$doc:meta])* $id:ident, $($(#[$doc2:meta])* $fl:ident => $name:ident),* $(,)?) => {
         $(#[$doc])*
-        #[derive(Debug, PartialEq, Eq)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum $id {
             $($(#[$doc2])* $name),*
         }
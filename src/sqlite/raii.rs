@@ -1,9 +1,13 @@
 use libsqlite3_sys::{self as ffi};
-use std::ffi::CStr;
+use std::{
+    ffi::CStr,
+    io::{self, Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+};
 
 use super::{
-    Database, DatabaseExt, OpenFlag, Statement, StatementExt,
-    error::{OpenError, PrepareError},
+    Database, DatabaseError, DatabaseExt, OpenFlag, Statement, StatementExt,
+    error::{BlobError, OpenError, PrepareError},
 };
 use crate::SqliteStr;
 
@@ -95,6 +99,198 @@ impl Drop for StatementHandle {
     }
 }
 
+/// An RAII implementation of a [`sqlite3_blob`][1] object.
+///
+/// When this structure is dropped (falls out of scope), `sqlite3_blob` will be [`close`][2].
+///
+/// It implements [`Read`], [`Write`] and [`Seek`] for incremental blob I/O, the cursor
+/// starts at the beginning of the blob.
+///
+/// Note that blob size cannot be changed via this handle, writing beyond the blob size
+/// returns 0 bytes written. Use [`bind_zeroblob`][3] to reserve the blob size.
+///
+/// The handle borrows the database it opened from for `'db`, see [`open`][BlobHandle::open].
+///
+/// [1]: <https://sqlite.org/c3ref/blob.html>
+/// [2]: <https://sqlite.org/c3ref/blob_close.html>
+/// [3]: super::StatementExt::bind_zeroblob
+#[derive(Debug)]
+pub struct BlobHandle<'db> {
+    blob: *mut ffi::sqlite3_blob,
+    db: *mut ffi::sqlite3,
+    offset: usize,
+    _p: PhantomData<&'db ()>,
+}
+
+impl<'db> BlobHandle<'db> {
+    /// Open a blob at given database, table, column and rowid.
+    ///
+    /// Database name is `main` for the main database, or the name of attached database.
+    ///
+    /// When `db` is a reference, the handle cannot outlive it, raw pointer is not tracked,
+    /// so it is caller responsibility to close the handle before the database.
+    ///
+    /// this is a wrapper for `sqlite3_blob_open()`
+    pub fn open<DB: Database + 'db>(
+        db: DB,
+        database: &CStr,
+        table: &CStr,
+        column: &CStr,
+        rowid: i64,
+        writable: bool,
+    ) -> Result<Self, BlobError> {
+        let db = db.as_ptr();
+        let mut blob = std::ptr::null_mut();
+        let result = unsafe {
+            ffi::sqlite3_blob_open(
+                db,
+                database.as_ptr(),
+                table.as_ptr(),
+                column.as_ptr(),
+                rowid,
+                writable as _,
+                &mut blob,
+            )
+        };
+        if result != ffi::SQLITE_OK {
+            // blob is set to null on error, except for `SQLITE_MISUSE`
+            unsafe { ffi::sqlite3_blob_close(blob) };
+            return Err(DatabaseError::from_code(result, db).into());
+        }
+        Ok(Self {
+            blob,
+            db,
+            offset: 0,
+            _p: PhantomData,
+        })
+    }
+
+    /// Move the handle to another row of the same table, the cursor is reset to the beginning.
+    ///
+    /// this is a wrapper for `sqlite3_blob_reopen()`
+    pub fn reopen(&mut self, rowid: i64) -> Result<(), BlobError> {
+        let result = unsafe { ffi::sqlite3_blob_reopen(self.blob, rowid) };
+        self.check(result)?;
+        self.offset = 0;
+        Ok(())
+    }
+
+    /// Returns the size of the blob in bytes.
+    ///
+    /// this is a wrapper for `sqlite3_blob_bytes()`
+    pub fn len(&self) -> usize {
+        unsafe { ffi::sqlite3_blob_bytes(self.blob) as _ }
+    }
+
+    /// Returns `true` if the blob is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read exactly `buf.len()` bytes starting at given offset, regardless of the cursor.
+    ///
+    /// Reading beyond the blob size returns error.
+    ///
+    /// this is a wrapper for `sqlite3_blob_read()`
+    pub fn read_at(&self, buf: &mut [u8], offset: usize) -> Result<(), BlobError> {
+        let (len, offset) = Self::range(buf.len(), offset)?;
+        let result = unsafe { ffi::sqlite3_blob_read(self.blob, buf.as_mut_ptr().cast(), len, offset) };
+        self.check(result)
+    }
+
+    /// Write all of `data` starting at given offset, regardless of the cursor.
+    ///
+    /// Writing beyond the blob size returns error.
+    ///
+    /// this is a wrapper for `sqlite3_blob_write()`
+    pub fn write_at(&self, data: &[u8], offset: usize) -> Result<(), BlobError> {
+        let (len, offset) = Self::range(data.len(), offset)?;
+        let result = unsafe { ffi::sqlite3_blob_write(self.blob, data.as_ptr().cast(), len, offset) };
+        self.check(result)
+    }
+
+    /// Close the blob handle explicitly, returning any error instead of logging it.
+    ///
+    /// this is a wrapper for `sqlite3_blob_close()`
+    pub fn close(self) -> Result<(), BlobError> {
+        let me = std::mem::ManuallyDrop::new(self);
+        let result = unsafe { ffi::sqlite3_blob_close(me.blob) };
+        me.check(result)
+    }
+
+    fn check(&self, result: i32) -> Result<(), BlobError> {
+        match result {
+            ffi::SQLITE_OK => Ok(()),
+            result => Err(DatabaseError::from_code(result, self.db).into()),
+        }
+    }
+
+    /// sqlite blob api use `i32` for length and offset
+    fn range(len: usize, offset: usize) -> Result<(i32, i32), BlobError> {
+        match (i32::try_from(len), i32::try_from(offset)) {
+            (Ok(len), Ok(offset)) => Ok((len, offset)),
+            _ => Err(BlobError::OutOfRange),
+        }
+    }
+}
+
+impl Read for BlobHandle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.len().saturating_sub(self.offset));
+        if n == 0 {
+            return Ok(0);
+        }
+        self.read_at(&mut buf[..n], self.offset).map_err(io::Error::other)?;
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+impl Write for BlobHandle<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.len().saturating_sub(self.offset));
+        if n == 0 {
+            return Ok(0);
+        }
+        self.write_at(&buf[..n], self.offset).map_err(io::Error::other)?;
+        self.offset += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for BlobHandle<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let offset = match pos {
+            SeekFrom::Start(n) => i64::try_from(n).ok(),
+            SeekFrom::End(n) => (self.len() as i64).checked_add(n),
+            SeekFrom::Current(n) => (self.offset as i64).checked_add(n),
+        };
+        match offset {
+            Some(offset) if offset >= 0 => {
+                self.offset = offset as _;
+                Ok(offset as _)
+            }
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position")),
+        }
+    }
+}
+
+/// Close the blob handle
+impl Drop for BlobHandle<'_> {
+    fn drop(&mut self) {
+        let result = unsafe { ffi::sqlite3_blob_close(self.blob) };
+        if let Err(_err) = self.check(result) {
+            #[cfg(feature = "log")]
+            log::error!("Failed to close blob handle on drop: {_err}")
+        }
+    }
+}
+
 /// An RAII implementation of a [`sqlite3_mutex`][1] object.
 ///
 /// On creation, `sqlite3_mutex` will be in `enter` state.
@@ -1,5 +1,8 @@
 use libsqlite3_sys::{self as ffi};
-use std::ptr;
+use std::{
+    ffi::{CStr, c_int},
+    ptr,
+};
 
 use super::{
     DataType, DatabaseError, StepResult,
@@ -41,17 +44,109 @@ pub(super) use ffi_stmt;
 ///
 /// providing sql via cstr may benefit a small performance advantage
 ///
+/// Returns error if sql contains more than one statement, use [`prepare_v2_tail`] instead.
+///
 /// <https://sqlite.org/c3ref/prepare.html>
 pub fn prepare_v2<S: SqliteStr>(db: *mut ffi::sqlite3, sql: S) -> Result<*mut ffi::sqlite3_stmt, PrepareError> {
-    let mut stmt = ptr::null_mut();
     let (ptr, len, _) = sql.as_nulstr();
-    match ffi_db!(sqlite3_prepare_v2(db, ptr, len, &mut stmt, ptr::null_mut())) {
-        Ok(()) => {
-            #[cfg(feature = "log")]
-            log::debug!("prepared {sql:?}");
-            Ok(stmt)
-        },
-        Err(err) => Err(err),
+    let bytes = unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), len as usize) };
+    let (stmt, consumed) = prepare_v2_tail(db, bytes)?;
+
+    if blank_len(&bytes[consumed..]) != bytes.len() - consumed {
+        unsafe { ffi::sqlite3_finalize(stmt) };
+        return Err(PrepareError::TrailingSql { offset: consumed });
+    }
+
+    #[cfg(feature = "log")]
+    log::debug!("prepared {sql:?}");
+
+    Ok(stmt)
+}
+
+/// Create a prepared statement from the first statement in sql.
+///
+/// Returns the prepared statement and the number of bytes consumed,
+/// the rest of the sql can be used to prepare the next statement.
+///
+/// The returned statement is null if sql contains no statement, e.g: whitespace or comment.
+///
+/// this is a wrapper for `sqlite3_prepare_v2()`
+///
+/// <https://sqlite.org/c3ref/prepare.html>
+pub fn prepare_v2_tail(db: *mut ffi::sqlite3, sql: &[u8]) -> Result<(*mut ffi::sqlite3_stmt, usize), DatabaseError> {
+    let mut stmt = ptr::null_mut();
+    let mut tail = ptr::null();
+    let len = c_int::try_from(sql.len()).unwrap_or(c_int::MAX);
+
+    ffi_db!(sqlite3_prepare_v2(db, sql.as_ptr().cast(), len, &mut stmt, &mut tail) as _)?;
+
+    let consumed = match tail.is_null() {
+        true => sql.len(),
+        false => (unsafe { tail.cast::<u8>().offset_from(sql.as_ptr()) }) as usize,
+    };
+
+    Ok((stmt, consumed))
+}
+
+/// Returns the length of leading whitespace, comments, semicolons or nul in sql.
+pub(super) fn blank_len(sql: &[u8]) -> usize {
+    let mut i = 0;
+
+    while i < sql.len() {
+        match &sql[i..] {
+            [b'-', b'-', ..] => {
+                i += sql[i..].iter().position(|&b| b == b'\n').unwrap_or(sql.len() - i);
+            }
+            [b'/', b'*', rest @ ..] => {
+                i += 2 + rest.windows(2).position(|w| w == b"*/").map_or(rest.len(), |e| e + 2);
+            }
+            [b, ..] if b.is_ascii_whitespace() || matches!(b, b';' | b'\0') => i += 1,
+            _ => break,
+        }
+    }
+
+    i
+}
+
+/// Returns `true` if the first statement in sql is INSERT or REPLACE, including the one
+/// preceded by WITH clause.
+pub(crate) fn is_insert(sql: &[u8]) -> bool {
+    let mut i = 0;
+    let mut depth = 0usize;
+    let mut with = false;
+    let is_word = |b: &u8| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$') || !b.is_ascii();
+
+    loop {
+        i += blank_len(&sql[i..]);
+        match sql[i..] {
+            [] => return false,
+            [quote @ (b'\'' | b'"' | b'`' | b'['), ref rest @ ..] => {
+                let close = if quote == b'[' { b']' } else { quote };
+                i += rest.iter().position(|&b| b == close).map_or(sql.len() - i, |e| e + 2);
+            }
+            [b'(', ..] => (depth, i) = (depth + 1, i + 1),
+            [b')', ..] => (depth, i) = (depth.saturating_sub(1), i + 1),
+            [ref b, ..] if is_word(b) => {
+                let len = sql[i..].iter().position(|b| !is_word(b)).unwrap_or(sql.len() - i);
+                let word = &sql[i..i + len];
+                i += len;
+
+                if depth != 0 {
+                    continue;
+                }
+                if word.eq_ignore_ascii_case(b"insert") || word.eq_ignore_ascii_case(b"replace") {
+                    return true;
+                }
+                if !with && !word.eq_ignore_ascii_case(b"with") {
+                    return false;
+                }
+                if ["select", "values", "update", "delete"].iter().any(|kw| word.eq_ignore_ascii_case(kw.as_bytes())) {
+                    return false;
+                }
+                with = true;
+            }
+            _ => i += 1,
+        }
     }
 }
 
@@ -101,8 +196,52 @@ pub trait StatementExt: Statement {
         ffi_stmt!(sqlite3_clear_bindings(self.as_db_ptr(), self.as_stmt_ptr()))
     }
 
+    /// Returns the sql text used to create the statement.
+    ///
+    /// this is a wrapper for `sqlite3_sql()`
+    fn sql(&self) -> Option<&str> {
+        let sql = unsafe { ffi::sqlite3_sql(self.as_stmt_ptr()) };
+        match sql.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(sql) }.to_str().ok(),
+        }
+    }
+
     // NOTE: parameter encoding
 
+    /// Returns the number of SQL parameters.
+    ///
+    /// This routine actually returns the index of the largest (rightmost) parameter.
+    ///
+    /// this is a wrapper for `sqlite3_bind_parameter_count()`
+    fn bind_parameter_count(&self) -> i32 {
+        unsafe { ffi::sqlite3_bind_parameter_count(self.as_stmt_ptr()) }
+    }
+
+    /// Returns the index of an SQL parameter given its name, including its prefix (`:`, `@` or `$`).
+    ///
+    /// A zero is returned if no matching parameter is found.
+    ///
+    /// this is a wrapper for `sqlite3_bind_parameter_index()`
+    fn bind_parameter_index(&self, name: &CStr) -> i32 {
+        unsafe { ffi::sqlite3_bind_parameter_index(self.as_stmt_ptr(), name.as_ptr()) }
+    }
+
+    /// Returns the name of the parameter at given index, including its prefix.
+    ///
+    /// Returns `None` if the parameter is nameless (`?`) or index out of range.
+    ///
+    /// Note that parameter index is one based.
+    ///
+    /// this is a wrapper for `sqlite3_bind_parameter_name()`
+    fn bind_parameter_name(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_bind_parameter_name(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
     /// Bind integer to parameter at given index.
     ///
     /// Note that parameter index is one based.
@@ -110,6 +249,13 @@ pub trait StatementExt: Statement {
         ffi_stmt!(sqlite3_bind_int(self.as_db_ptr(), self.as_stmt_ptr(), idx, value))
     }
 
+    /// Bind 64-bit integer to parameter at given index.
+    ///
+    /// Note that parameter index is one based.
+    fn bind_int64(&self, idx: i32, value: i64) -> Result<(), BindError> {
+        ffi_stmt!(sqlite3_bind_int64(self.as_db_ptr(), self.as_stmt_ptr(), idx, value))
+    }
+
     /// Bind float to parameter at given index.
     ///
     /// Note that parameter index is one based.
@@ -126,6 +272,17 @@ pub trait StatementExt: Statement {
 
     // todo: maybe choose other than SQLITE_TRANSIENT
 
+    /// Bind a blob of given length filled with zeroes to parameter at given index.
+    ///
+    /// The content can be written later via [`BlobHandle`][super::BlobHandle].
+    ///
+    /// Note that parameter index is one based.
+    ///
+    /// this is a wrapper for `sqlite3_bind_zeroblob64()`
+    fn bind_zeroblob(&self, idx: i32, len: u64) -> Result<(), BindError> {
+        ffi_stmt!(sqlite3_bind_zeroblob64(self.as_db_ptr(), self.as_stmt_ptr(), idx, len))
+    }
+
     /// Bind text to parameter at given index.
     ///
     /// Note that parameter index is one based.
@@ -164,6 +321,86 @@ pub trait StatementExt: Statement {
         unsafe { ffi::sqlite3_data_count(self.as_stmt_ptr()) }
     }
 
+    /// Returns the name assigned to a particular column in the result set.
+    ///
+    /// The name of a result column is the value of the "AS" clause for that column, if there is an AS clause.
+    /// If there is no AS clause then the name of the column is unspecified and may change from one release
+    /// of SQLite to the next.
+    ///
+    /// Returns `None` if index out of range or allocation failed.
+    ///
+    /// this is a wrapper for `sqlite3_column_name()`
+    fn column_name(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_column_name(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
+    /// Returns the declared type of the table column of a result column.
+    ///
+    /// Returns `None` if the result column is an expression or subquery, or index out of range.
+    ///
+    /// this is a wrapper for `sqlite3_column_decltype()`
+    fn column_decltype(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_column_decltype(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
+    /// Returns the database name which is the origin of a result column.
+    ///
+    /// Returns `None` if the result column is an expression or subquery, or index out of range.
+    ///
+    /// Requires `column_metadata` feature, sqlite must be compiled with `SQLITE_ENABLE_COLUMN_METADATA`.
+    ///
+    /// this is a wrapper for `sqlite3_column_database_name()`
+    #[cfg(feature = "column_metadata")]
+    fn column_database_name(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_column_database_name(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
+    /// Returns the table name which is the origin of a result column.
+    ///
+    /// Returns `None` if the result column is an expression or subquery, or index out of range.
+    ///
+    /// Requires `column_metadata` feature, sqlite must be compiled with `SQLITE_ENABLE_COLUMN_METADATA`.
+    ///
+    /// this is a wrapper for `sqlite3_column_table_name()`
+    #[cfg(feature = "column_metadata")]
+    fn column_table_name(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_column_table_name(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
+    /// Returns the table column name which is the origin of a result column.
+    ///
+    /// Unlike [`column_name`][StatementExt::column_name], it is not affected by "AS" clause.
+    ///
+    /// Returns `None` if the result column is an expression or subquery, or index out of range.
+    ///
+    /// Requires `column_metadata` feature, sqlite must be compiled with `SQLITE_ENABLE_COLUMN_METADATA`.
+    ///
+    /// this is a wrapper for `sqlite3_column_origin_name()`
+    #[cfg(feature = "column_metadata")]
+    fn column_origin_name(&self, idx: i32) -> Option<&str> {
+        let name = unsafe { ffi::sqlite3_column_origin_name(self.as_stmt_ptr(), idx) };
+        match name.is_null() {
+            true => None,
+            false => unsafe { CStr::from_ptr(name) }.to_str().ok(),
+        }
+    }
+
     fn column_type(&self, idx: i32) -> DataType {
         let code = unsafe { ffi::sqlite3_column_type(self.as_stmt_ptr(), idx) };
         DataType::from_code(code).expect("sqlite return non datatype from `sqlite3_column_type`")
@@ -173,6 +410,10 @@ pub trait StatementExt: Statement {
         unsafe { ffi::sqlite3_column_int(self.as_stmt_ptr(), idx) }
     }
 
+    fn column_int64(&self, idx: i32) -> i64 {
+        unsafe { ffi::sqlite3_column_int64(self.as_stmt_ptr(), idx) }
+    }
+
     fn column_double(&self, idx: i32) -> f64 {
         unsafe { ffi::sqlite3_column_double(self.as_stmt_ptr(), idx) }
     }
@@ -187,9 +428,14 @@ pub trait StatementExt: Statement {
 
     fn column_blob(&self, idx: i32) -> &[u8] {
         unsafe {
+            // `sqlite3_column_bytes()` should be called after `sqlite3_column_blob()`
+            let data = ffi::sqlite3_column_blob(self.as_stmt_ptr(), idx).cast::<u8>();
             let len = self.column_bytes(idx) as usize;
-            let data = ffi::sqlite3_column_blob(self.as_stmt_ptr(), idx).cast();
-            std::slice::from_raw_parts(data, len)
+            match data.is_null() {
+                // zero length blob returns null pointer
+                true => &[],
+                false => std::slice::from_raw_parts(data, len),
+            }
         }
     }
 
@@ -0,0 +1,223 @@
+use crate::{
+    Connection, Result, SqliteStr,
+    query::{Execute, StatementRef},
+    sqlite::DatabaseExt,
+};
+
+/// Transaction behavior.
+///
+/// <https://sqlite.org/lang_transaction.html>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionBehavior {
+    /// The transaction does not actually start until the database is first accessed.
+    #[default]
+    Deferred,
+    /// Start a new write immediately, without waiting for a write statement.
+    Immediate,
+    /// Same as `Immediate`, but prevent other connection from reading the database
+    /// in journal modes other than WAL.
+    Exclusive,
+}
+
+impl TransactionBehavior {
+    fn begin_sql(self) -> &'static std::ffi::CStr {
+        match self {
+            TransactionBehavior::Deferred => c"BEGIN DEFERRED",
+            TransactionBehavior::Immediate => c"BEGIN IMMEDIATE",
+            TransactionBehavior::Exclusive => c"BEGIN EXCLUSIVE",
+        }
+    }
+}
+
+/// An RAII guard of a database transaction.
+///
+/// When this structure is dropped (falls out of scope) without [`commit`][Transaction::commit],
+/// the transaction will be rolled back.
+///
+/// This structure is created by [`Connection::transaction`].
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// let mut db = Connection::open_in_memory()?;
+/// rhosql::query("create table post(name)", &mut db).execute()?;
+///
+/// let mut tx = db.transaction()?;
+/// rhosql::query("insert into post(name) values(?1)", &mut tx).bind("Control").execute()?;
+/// tx.commit()?;
+///
+/// let mut tx = db.transaction()?;
+/// rhosql::query("insert into post(name) values(?1)", &mut tx).bind("Vanish").execute()?;
+/// drop(tx);
+///
+/// let posts = rhosql::query("select name from post", &mut db).fetch_all::<(String,)>()?;
+/// assert_eq!(posts.len(), 1);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Transaction<'c> {
+    conn: &'c mut Connection,
+    done: bool,
+}
+
+impl<'c> Transaction<'c> {
+    pub(crate) fn begin(conn: &'c mut Connection, behavior: TransactionBehavior) -> Result<Self> {
+        crate::query(behavior.begin_sql(), &mut *conn).execute()?;
+        Ok(Self { conn, done: false })
+    }
+
+    /// Commit the transaction.
+    pub fn commit(mut self) -> Result<()> {
+        crate::query(c"COMMIT", &mut *self.conn).execute()?;
+        self.done = true;
+        Ok(())
+    }
+
+    /// Create a savepoint inside the transaction.
+    ///
+    /// See [`Savepoint`] for more details.
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        Savepoint::begin(self.conn, 1)
+    }
+
+    /// Rollback the transaction.
+    pub fn rollback(mut self) -> Result<()> {
+        self.done = true;
+        self.rollback_inner()
+    }
+
+    fn rollback_inner(&mut self) -> Result<()> {
+        // transaction may already rolled back automatically by sqlite on some error
+        if self.conn.get_autocommit() {
+            return Ok(());
+        }
+        crate::query(c"ROLLBACK", &mut *self.conn).execute()?;
+        Ok(())
+    }
+}
+
+impl<'s> Execute<'s> for &'s mut Transaction<'_> {
+    fn prepare<S: SqliteStr>(self, sql: S) -> Result<StatementRef<'s>> {
+        self.conn.prepare(sql)
+    }
+}
+
+/// Rollback the transaction if not yet committed
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        if let Err(_err) = self.rollback_inner() {
+            #[cfg(feature = "log")]
+            log::error!("Failed to rollback transaction on drop: {_err}")
+        }
+    }
+}
+
+/// An RAII guard of a savepoint.
+///
+/// Savepoint can be nested by calling [`savepoint`][Savepoint::savepoint] on the guard,
+/// each savepoint is named uniquely by its depth.
+///
+/// When this structure is dropped (falls out of scope) without [`release`][Savepoint::release],
+/// all changes since the savepoint created will be rolled back.
+///
+/// This structure is created by [`Connection::savepoint`] or [`Transaction::savepoint`].
+///
+/// <https://sqlite.org/lang_savepoint.html>
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// # use rhosql::Connection;
+/// let mut db = Connection::open_in_memory()?;
+/// rhosql::query("create table post(name)", &mut db).execute()?;
+///
+/// let mut tx = db.transaction()?;
+/// rhosql::query("insert into post(name) values(?1)", &mut tx).bind("Control").execute()?;
+///
+/// let mut sp = tx.savepoint()?;
+/// rhosql::query("insert into post(name) values(?1)", &mut sp).bind("Vanish").execute()?;
+/// sp.rollback()?;
+///
+/// tx.commit()?;
+///
+/// let posts = rhosql::query("select name from post", &mut db).fetch_all::<(String,)>()?;
+/// assert_eq!(posts.len(), 1);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Savepoint<'c> {
+    conn: &'c mut Connection,
+    depth: u32,
+    done: bool,
+}
+
+impl<'c> Savepoint<'c> {
+    pub(crate) fn begin(conn: &'c mut Connection, depth: u32) -> Result<Self> {
+        exec(conn, "SAVEPOINT", depth)?;
+        Ok(Self { conn, depth, done: false })
+    }
+
+    /// Create a nested savepoint.
+    pub fn savepoint(&mut self) -> Result<Savepoint<'_>> {
+        Savepoint::begin(self.conn, self.depth + 1)
+    }
+
+    /// Release the savepoint, changes will be committed by the outer transaction.
+    ///
+    /// If there is no outer transaction, changes will be committed to the database.
+    pub fn release(mut self) -> Result<()> {
+        exec(self.conn, "RELEASE", self.depth)?;
+        self.done = true;
+        Ok(())
+    }
+
+    /// Rollback all changes since the savepoint created.
+    pub fn rollback(mut self) -> Result<()> {
+        self.done = true;
+        self.rollback_inner()
+    }
+
+    fn rollback_inner(&mut self) -> Result<()> {
+        // savepoint may already rolled back automatically by sqlite on some error
+        if self.conn.get_autocommit() {
+            return Ok(());
+        }
+        exec(self.conn, "ROLLBACK TO", self.depth)?;
+        exec(self.conn, "RELEASE", self.depth)
+    }
+}
+
+/// execute savepoint command, savepoint name is generated from its depth
+///
+/// the possible names is bounded by depth, so its fine to be cached
+fn exec(conn: &mut Connection, command: &str, depth: u32) -> Result<()> {
+    crate::query(format!("{command} _rhosql_sp{depth}"), conn).execute()?;
+    Ok(())
+}
+
+impl<'s> Execute<'s> for &'s mut Savepoint<'_> {
+    fn prepare<S: SqliteStr>(self, sql: S) -> Result<StatementRef<'s>> {
+        self.conn.prepare(sql)
+    }
+}
+
+/// Rollback to the savepoint if not yet released
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        if let Err(_err) = self.rollback_inner() {
+            #[cfg(feature = "log")]
+            log::error!("Failed to rollback savepoint on drop: {_err}")
+        }
+    }
+}
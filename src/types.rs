@@ -0,0 +1,7 @@
+//! Integration with third party types, each behind its own feature flag.
+
+#[cfg(feature = "time")]
+pub mod time;
+
+#[cfg(feature = "serde")]
+pub mod json;
@@ -0,0 +1,260 @@
+//! Integration with [`serde`] crate via [`serde_json`], requires `serde` feature.
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::{
+    Decode, Encode, Encoded, Result, Value, ValueRef,
+    sqlite::{
+        DataType,
+        error::{BindError, DecodeError},
+    },
+};
+
+/// A json value.
+///
+/// Decoded from TEXT as json text, or BLOB as sqlite [JSONB][jsonb], into any
+/// [`Deserialize`][serde::Deserialize] type. Encoded as json text from any [`Serialize`] type,
+/// use sql `jsonb(?)` to store it as JSONB, which requires sqlite 3.45.0.
+///
+/// [jsonb]: https://sqlite.org/jsonb.html
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use rhosql::types::json::Json;
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Debug, rhosql::FromRow)]
+/// struct Post {
+///     tags: Json<Vec<String>>,
+///     meta: Json<BTreeMap<String, i64>>,
+/// }
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+/// rhosql::query("create table post(tags text, meta text)", &mut db).execute()?;
+///
+/// let meta = BTreeMap::from([("views".to_string(), 420)]);
+///
+/// rhosql::query("insert into post values(?1, ?2)", &mut db)
+///     .bind(Json(["rust", "sqlite"]))
+///     .bind(Json(&meta))
+///     .execute()?;
+///
+/// let post = rhosql::query("select * from post", &mut db).fetch_optional::<Post>()?.unwrap();
+/// assert_eq!(post.tags.0, ["rust", "sqlite"]);
+/// assert_eq!(post.meta.0, meta);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T: DeserializeOwned> Decode<'_> for Json<T> {
+    fn decode(value: ValueRef) -> Result<Self> {
+        let result = match value {
+            ValueRef::Text(text) => serde_json::from_str(text),
+            ValueRef::Blob(blob) => serde_json::from_value(jsonb::decode(blob)?),
+            _ => Err(DecodeError::InvalidDataType { expect: DataType::Text, found: value.data_type() })?,
+        };
+        match result {
+            Ok(ok) => Ok(Self(ok)),
+            Err(err) => Err(DecodeError::Custom(format!("failed to decode json: {err}")).into()),
+        }
+    }
+}
+
+impl<'a, T: Serialize> Encode<'a> for Json<T> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        (&self).encode()
+    }
+}
+
+impl<'a, T: Serialize> Encode<'a> for &Json<T> {
+    fn encode(self) -> Result<Encoded<'a>> {
+        match serde_json::to_string(&self.0) {
+            Ok(json) => Ok(Encoded::Owned(Value::Text(json))),
+            Err(err) => Err(BindError::Custom(format!("failed to encode json: {err}")).into()),
+        }
+    }
+}
+
+/// sqlite binary json format
+///
+/// <https://sqlite.org/jsonb.html>
+pub(crate) mod jsonb {
+    use super::*;
+
+    const NULL: u8 = 0;
+    const TRUE: u8 = 1;
+    const FALSE: u8 = 2;
+    const INT: u8 = 3;
+    const INT5: u8 = 4;
+    const FLOAT: u8 = 5;
+    const FLOAT5: u8 = 6;
+    const TEXT: u8 = 7;
+    const TEXTJ: u8 = 8;
+    const TEXT5: u8 = 9;
+    const TEXTRAW: u8 = 10;
+    const ARRAY: u8 = 11;
+    const OBJECT: u8 = 12;
+
+    fn malformed() -> DecodeError {
+        DecodeError::Custom("failed to decode json: malformed jsonb".into())
+    }
+
+    /// decode a single jsonb element, the whole input must be consumed
+    pub fn decode(mut input: &[u8]) -> Result<JsonValue, DecodeError> {
+        let value = element(&mut input)?;
+        match input.is_empty() {
+            true => Ok(value),
+            false => Err(malformed()),
+        }
+    }
+
+    /// decode the input only when it is a well formed jsonb array
+    pub fn decode_array(input: &[u8]) -> Option<JsonValue> {
+        match input.first() {
+            Some(first) if first & 0x0f == ARRAY => decode(input).ok(),
+            _ => None,
+        }
+    }
+
+    /// returns element type and its payload, and advance the input
+    fn header<'a>(input: &mut &'a [u8]) -> Result<(u8, &'a [u8]), DecodeError> {
+        let (&first, rest) = input.split_first().ok_or_else(malformed)?;
+        let (ty, size) = (first & 0x0f, first >> 4);
+        let (len, rest) = match size {
+            0..=11 => (size as u64, rest),
+            _ => {
+                let n = 1 << (size - 12);
+                let bytes = rest.get(..n).ok_or_else(malformed)?;
+                let len = bytes.iter().fold(0u64, |acc, b| acc << 8 | u64::from(*b));
+                (len, &rest[n..])
+            }
+        };
+        let len = usize::try_from(len).map_err(|_| malformed())?;
+        let payload = rest.get(..len).ok_or_else(malformed)?;
+        *input = &rest[len..];
+        Ok((ty, payload))
+    }
+
+    fn text(payload: &[u8]) -> Result<&str, DecodeError> {
+        std::str::from_utf8(payload).map_err(DecodeError::Utf8)
+    }
+
+    fn element(input: &mut &[u8]) -> Result<JsonValue, DecodeError> {
+        let (ty, payload) = header(input)?;
+        let value = match ty {
+            NULL => JsonValue::Null,
+            TRUE => JsonValue::Bool(true),
+            FALSE => JsonValue::Bool(false),
+            INT => serde_json::from_str(text(payload)?).map_err(|_| malformed())?,
+            // out of range float, e.g: `Infinity` is stored as `9e999`
+            FLOAT => match serde_json::from_str(text(payload)?) {
+                Ok(value) => value,
+                Err(_) => float5(text(payload)?).ok_or_else(malformed)?,
+            },
+            INT5 => int5(text(payload)?).ok_or_else(malformed)?,
+            FLOAT5 => float5(text(payload)?).ok_or_else(malformed)?,
+            TEXT | TEXTRAW => JsonValue::String(text(payload)?.into()),
+            TEXTJ | TEXT5 => JsonValue::String(unescape(text(payload)?).ok_or_else(malformed)?),
+            ARRAY => {
+                let mut payload = payload;
+                let mut array = vec![];
+                while !payload.is_empty() {
+                    array.push(element(&mut payload)?);
+                }
+                JsonValue::Array(array)
+            }
+            OBJECT => {
+                let mut payload = payload;
+                let mut object = Map::new();
+                while !payload.is_empty() {
+                    let JsonValue::String(key) = element(&mut payload)? else {
+                        return Err(malformed());
+                    };
+                    object.insert(key, element(&mut payload)?);
+                }
+                JsonValue::Object(object)
+            }
+            _ => return Err(malformed()),
+        };
+        Ok(value)
+    }
+
+    /// json5 integer, e.g: `0x1F`, `+1`
+    fn int5(text: &str) -> Option<JsonValue> {
+        let (negative, digits) = match text.as_bytes().first()? {
+            b'-' => (true, &text[1..]),
+            b'+' => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let digits = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X"));
+        let int = match digits {
+            Some(hex) => i128::from_str_radix(hex, 16).ok()?,
+            None => text.trim_start_matches('+').parse().ok()?,
+        };
+        let int = if negative && digits.is_some() { -int } else { int };
+        match (i64::try_from(int), u64::try_from(int)) {
+            (Ok(int), _) => Some(int.into()),
+            (_, Ok(int)) => Some(int.into()),
+            _ => Number::from_f64(int as f64).map(JsonValue::Number),
+        }
+    }
+
+    /// json5 float, e.g: `.5`, `5.`, `Infinity`, `NaN`
+    fn float5(text: &str) -> Option<JsonValue> {
+        let float: f64 = text.trim_start_matches('+').parse().ok()?;
+        // json has no representation for non finite number
+        Some(Number::from_f64(float).map_or(JsonValue::Null, JsonValue::Number))
+    }
+
+    /// unescape json and json5 string escapes
+    fn unescape(text: &str) -> Option<String> {
+        let mut output = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                output.push(ch);
+                continue;
+            }
+            let ch = match chars.next()? {
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'v' => '\u{b}',
+                '0' => '\0',
+                'x' => char::from_u32(hex(&mut chars, 2)?)?,
+                'u' => match hex(&mut chars, 4)? {
+                    high @ 0xd800..=0xdbff => {
+                        chars = chars.as_str().strip_prefix("\\u")?.chars();
+                        let low = hex(&mut chars, 4)?.checked_sub(0xdc00).filter(|e| *e < 0x400)?;
+                        char::from_u32(0x10000 + ((high - 0xd800) << 10) + low)?
+                    }
+                    code => char::from_u32(code)?,
+                },
+                // line continuation
+                '\r' => {
+                    if chars.as_str().starts_with('\n') {
+                        chars.next();
+                    }
+                    continue;
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => continue,
+                ch => ch,
+            };
+            output.push(ch);
+        }
+        Some(output)
+    }
+
+    fn hex(chars: &mut std::str::Chars, len: usize) -> Option<u32> {
+        let rest = chars.as_str();
+        let code = u32::from_str_radix(rest.get(..len)?, 16).ok()?;
+        *chars = rest[len..].chars();
+        Some(code)
+    }
+}
@@ -0,0 +1,406 @@
+//! Integration with [`time`] crate, requires `time` feature.
+//!
+//! [`OffsetDateTime`], [`PrimitiveDateTime`] and [`Date`] can be decoded from all
+//! [sqlite date storage][storage]:
+//!
+//! - TEXT as ISO-8601, e.g: `2025-04-20 13:37:00.123`, with optional `T` separator and
+//!   optional timezone suffix, `Z` or `+07:00`
+//! - INTEGER as unix timestamp in seconds
+//! - REAL as julian day number
+//!
+//! By default, they are encoded as TEXT in UTC, the same format as sqlite `datetime()`,
+//! with fractional seconds only when non zero, e.g: `2025-04-20 13:37:00`. This format is
+//! fixed, so stored values compare and sort correctly as TEXT and with sqlite date functions.
+//! Use [`Iso8601`] to encode TEXT with `T` separator and the timezone offset instead, or
+//! [`Unix`] and [`Julian`] to choose the other storage.
+//!
+//! Values without timezone are assumed to be UTC, as sqlite does.
+//!
+//! [storage]: https://www.sqlite.org/lang_datefunc.html
+//!
+//! # Example
+//!
+//! ```
+//! # fn main() -> rhosql::Result<()> {
+//! use rhosql::types::time::Unix;
+//! use time::{Date, Month, OffsetDateTime};
+//!
+//! #[derive(Debug, rhosql::FromRow)]
+//! struct Post {
+//!     created_at: OffsetDateTime,
+//!     updated_at: Unix<OffsetDateTime>,
+//!     published: Date,
+//! }
+//!
+//! let mut db = rhosql::Connection::open_in_memory()?;
+//! let now = OffsetDateTime::from_unix_timestamp(1745156220).unwrap();
+//!
+//! let (text, int) = rhosql::query("select ?1, ?2", &mut db)
+//!     .bind(now)
+//!     .bind(Unix(now))
+//!     .fetch_optional::<(String, i64)>()?
+//!     .unwrap();
+//! assert_eq!(text, "2025-04-20 13:37:00");
+//! assert_eq!(int, 1745156220);
+//!
+//! let post = rhosql::query("select ?1, ?2, julianday(?1)", &mut db)
+//!     .bind(now)
+//!     .bind(Unix(now))
+//!     .fetch_optional::<Post>()?
+//!     .unwrap();
+//! assert_eq!(post.created_at, now);
+//! assert_eq!(post.updated_at.0, now);
+//! assert_eq!(post.published, Date::from_calendar_date(2025, Month::April, 20).unwrap());
+//! #   Ok(())
+//! # }
+//! ```
+use ::time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::{
+    Decode, Encode, Encoded, Result, Value, ValueRef,
+    sqlite::{
+        DataType,
+        error::{BindError, DecodeError},
+    },
+};
+
+/// Julian day number of unix epoch.
+const UNIX_EPOCH_JULIAN: f64 = 2_440_587.5;
+
+const NANOS_PER_DAY: f64 = 86_400_000_000_000.0;
+
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// Store date time as ISO-8601 TEXT, e.g: `2025-04-20T13:37:00.123+07:00`.
+///
+/// [`OffsetDateTime`] keeps its offset, written as `Z` for UTC, [`PrimitiveDateTime`] is
+/// written without offset. Fractional seconds is written only when non zero.
+///
+/// Note that decoding [`OffsetDateTime`] always returns UTC, and TEXT with different offsets
+/// do not compare correctly in sqlite, use the default format for column that is compared.
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use rhosql::types::time::Iso8601;
+/// use time::{OffsetDateTime, UtcOffset};
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+/// let now = OffsetDateTime::from_unix_timestamp(1745156220).unwrap();
+/// let local = now.to_offset(UtcOffset::from_hms(7, 0, 0).unwrap());
+///
+/// let (utc, text, datetime) = rhosql::query("select ?1, ?2, ?2", &mut db)
+///     .bind(Iso8601(now))
+///     .bind(Iso8601(local))
+///     .fetch_optional::<(String, String, Iso8601<OffsetDateTime>)>()?
+///     .unwrap();
+/// assert_eq!(utc, "2025-04-20T13:37:00Z");
+/// assert_eq!(text, "2025-04-20T20:37:00+07:00");
+/// assert_eq!(datetime.0, now);
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Iso8601<T>(pub T);
+
+/// Store date time as INTEGER unix timestamp in seconds.
+///
+/// Note that fractional seconds is truncated on encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Unix<T>(pub T);
+
+/// Store date time as REAL julian day number.
+///
+/// Note that precision is limited to milliseconds, julian day is decoded as whole milliseconds
+/// the same way as sqlite date functions, so value written by sqlite round-trip exactly.
+///
+/// ```
+/// # fn main() -> rhosql::Result<()> {
+/// use rhosql::types::time::Julian;
+/// use time::OffsetDateTime;
+///
+/// let mut db = rhosql::Connection::open_in_memory()?;
+/// let now = OffsetDateTime::from_unix_timestamp_nanos(1_745_156_220_123_000_000).unwrap();
+///
+/// let sql = "select julianday('2025-04-20 13:37:00.123'), ?1, strftime('%Y-%m-%d %H:%M:%f', ?1)";
+/// let (day, datetime, text) = rhosql::query(sql, &mut db)
+///     .bind(Julian(now))
+///     .fetch_optional::<(OffsetDateTime, Julian<OffsetDateTime>, String)>()?
+///     .unwrap();
+/// assert_eq!(day, now);
+/// assert_eq!(datetime.0, now);
+/// assert_eq!(text, "2025-04-20 13:37:00.123");
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Julian<T>(pub T);
+
+// NOTE: parsing
+
+/// minimal parser for sqlite date time text
+struct Parser<'a> {
+    input: &'a [u8],
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, ch: u8) -> bool {
+        match self.input.split_first() {
+            Some((first, rest)) if *first == ch => {
+                self.input = rest;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn digits(&mut self, len: usize) -> Option<u32> {
+        let digits = self.input.get(..len)?;
+        let mut value = 0;
+        for digit in digits {
+            if !digit.is_ascii_digit() {
+                return None;
+            }
+            value = value * 10 + u32::from(digit - b'0');
+        }
+        self.input = &self.input[len..];
+        Some(value)
+    }
+
+    /// `YYYY-MM-DD`
+    fn date(&mut self) -> Option<Date> {
+        let year = self.digits(4)?;
+        self.eat(b'-').then_some(())?;
+        let month = self.digits(2)?;
+        self.eat(b'-').then_some(())?;
+        let day = self.digits(2)?;
+        Date::from_calendar_date(year as _, Month::try_from(month as u8).ok()?, day as _).ok()
+    }
+
+    /// `HH:MM`, `HH:MM:SS` or `HH:MM:SS.SSS`
+    fn time(&mut self) -> Option<Time> {
+        let hour = self.digits(2)?;
+        self.eat(b':').then_some(())?;
+        let minute = self.digits(2)?;
+        let mut second = 0;
+        let mut nano = 0;
+        if self.eat(b':') {
+            second = self.digits(2)?;
+            if self.eat(b'.') {
+                let len = self.input.iter().take_while(|e|e.is_ascii_digit()).count();
+                if len == 0 {
+                    return None;
+                }
+                // digits beyond nanosecond are ignored
+                let mut frac = self.digits(len.min(9))?;
+                for _ in len..9 {
+                    frac *= 10;
+                }
+                self.digits(len.saturating_sub(9))?;
+                nano = frac;
+            }
+        }
+        Time::from_hms_nano(hour as _, minute as _, second as _, nano).ok()
+    }
+
+    /// `Z`, `+HH:MM` or `-HH:MM`
+    fn offset(&mut self) -> Option<UtcOffset> {
+        if self.eat(b'Z') || self.eat(b'z') {
+            return Some(UtcOffset::UTC);
+        }
+        let sign = match () {
+            _ if self.eat(b'+') => 1,
+            _ if self.eat(b'-') => -1,
+            _ => return None,
+        };
+        let hour = self.digits(2)? as i8;
+        self.eat(b':').then_some(())?;
+        let minute = self.digits(2)? as i8;
+        UtcOffset::from_hms(sign * hour, sign * minute, 0).ok()
+    }
+
+    /// parse the whole input, date only and time only is accepted,
+    /// where time only assume date `2000-01-01` as sqlite does
+    fn datetime(mut self) -> Option<OffsetDateTime> {
+        let (date, time) = match self.input.get(2) {
+            Some(b':') => (Date::from_calendar_date(2000, Month::January, 1).ok()?, self.time()?),
+            _ => {
+                let date = self.date()?;
+                let time = match (self.input.first(), self.input.get(3)) {
+                    (Some(b'T' | b't' | b' '), Some(b':')) => {
+                        self.input = &self.input[1..];
+                        self.time()?
+                    }
+                    _ => Time::MIDNIGHT,
+                };
+                (date, time)
+            }
+        };
+        while self.eat(b' ') { }
+        let offset = match self.input.is_empty() {
+            true => UtcOffset::UTC,
+            false => self.offset()?,
+        };
+        self.input.is_empty().then_some(())?;
+        Some(PrimitiveDateTime::new(date, time).assume_offset(offset))
+    }
+}
+
+/// decode any sqlite date storage into utc date time
+fn decode_utc(value: ValueRef, ty: &'static str) -> Result<OffsetDateTime> {
+    let datetime = match value {
+        ValueRef::Text(text) => Parser { input: text.trim().as_bytes() }.datetime().ok_or_else(|| {
+            DecodeError::InvalidFormat { ty, value: text.into() }
+        })?,
+        ValueRef::Int(int) => OffsetDateTime::from_unix_timestamp(int)
+            .map_err(|_| DecodeError::Overflow { value: int, ty })?,
+        ValueRef::Float(day) => {
+            // sqlite computes julian day as whole milliseconds
+            let millis = (day * MILLIS_PER_DAY).round() - UNIX_EPOCH_JULIAN * MILLIS_PER_DAY;
+            OffsetDateTime::from_unix_timestamp_nanos((millis as i128).saturating_mul(1_000_000))
+                .ok()
+                .filter(|_| millis.is_finite())
+                .ok_or_else(|| DecodeError::InvalidFormat { ty, value: day.to_string() })?
+        }
+        _ => Err(DecodeError::InvalidDataType { expect: DataType::Text, found: value.data_type() })?,
+    };
+    Ok(datetime.to_offset(UtcOffset::UTC))
+}
+
+// NOTE: formatting
+
+/// sqlite date functions only support year 0000 to 9999
+fn check_year(date: Date, ty: &'static str) -> Result<(), BindError> {
+    match (0..=9999).contains(&date.year()) {
+        true => Ok(()),
+        false => Err(BindError::Overflow { ty }),
+    }
+}
+
+fn format_date(date: Date) -> String {
+    format!("{:04}-{:02}-{:02}", date.year(), date.month() as u8, date.day())
+}
+
+fn format_datetime(datetime: PrimitiveDateTime, separator: char) -> String {
+    let mut output = format!(
+        "{}{separator}{:02}:{:02}:{:02}",
+        format_date(datetime.date()),
+        datetime.hour(),
+        datetime.minute(),
+        datetime.second(),
+    );
+    let nano = datetime.nanosecond();
+    if nano != 0 {
+        let frac = format!("{nano:09}");
+        output.push('.');
+        output.push_str(frac.trim_end_matches('0'));
+    }
+    output
+}
+
+/// `Z` or `+HH:MM`, offset with seconds is not supported by sqlite
+fn format_offset(offset: UtcOffset) -> String {
+    match offset.is_utc() {
+        true => "Z".into(),
+        false => {
+            let sign = if offset.is_negative() { '-' } else { '+' };
+            format!("{sign}{:02}:{:02}", offset.whole_hours().abs(), offset.minutes_past_hour().abs())
+        }
+    }
+}
+
+fn to_utc(datetime: OffsetDateTime) -> PrimitiveDateTime {
+    let datetime = datetime.to_offset(UtcOffset::UTC);
+    PrimitiveDateTime::new(datetime.date(), datetime.time())
+}
+
+// NOTE: impls
+
+impl Decode<'_> for OffsetDateTime {
+    fn decode(value: ValueRef) -> Result<Self> {
+        decode_utc(value, "OffsetDateTime")
+    }
+}
+
+impl Decode<'_> for PrimitiveDateTime {
+    fn decode(value: ValueRef) -> Result<Self> {
+        decode_utc(value, "PrimitiveDateTime").map(to_utc)
+    }
+}
+
+impl Decode<'_> for Date {
+    fn decode(value: ValueRef) -> Result<Self> {
+        decode_utc(value, "Date").map(OffsetDateTime::date)
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Iso8601<T> {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        T::decode(value).map(Self)
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Unix<T> {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        T::decode(value).map(Self)
+    }
+}
+
+impl<'a, T: Decode<'a>> Decode<'a> for Julian<T> {
+    fn decode(value: ValueRef<'a>) -> Result<Self> {
+        T::decode(value).map(Self)
+    }
+}
+
+macro_rules! encode {
+    ($ty:ty, $me:ident => $expr:expr) => {
+        impl<'a> Encode<'a> for $ty {
+            fn encode(self) -> Result<Encoded<'a>> {
+                let $me = self;
+                Ok($expr)
+            }
+        }
+        impl<'a> Encode<'a> for &$ty {
+            fn encode(self) -> Result<Encoded<'a>> {
+                <$ty as Encode>::encode(*self)
+            }
+        }
+    };
+}
+
+encode!(OffsetDateTime, me => PrimitiveDateTime::encode(to_utc(me))?);
+encode!(PrimitiveDateTime, me => {
+    check_year(me.date(), "PrimitiveDateTime")?;
+    Encoded::Owned(Value::Text(format_datetime(me, ' ')))
+});
+encode!(Date, me => {
+    check_year(me, "Date")?;
+    Encoded::Owned(Value::Text(format_date(me)))
+});
+
+encode!(Iso8601<OffsetDateTime>, me => {
+    let datetime = match me.0.offset().seconds_past_minute() {
+        0 => me.0,
+        _ => me.0.to_offset(UtcOffset::UTC),
+    };
+    let primitive = PrimitiveDateTime::new(datetime.date(), datetime.time());
+    check_year(primitive.date(), "OffsetDateTime")?;
+    let mut text = format_datetime(primitive, 'T');
+    text.push_str(&format_offset(datetime.offset()));
+    Encoded::Owned(Value::Text(text))
+});
+encode!(Iso8601<PrimitiveDateTime>, me => {
+    check_year(me.0.date(), "PrimitiveDateTime")?;
+    Encoded::Owned(Value::Text(format_datetime(me.0, 'T')))
+});
+encode!(Iso8601<Date>, me => me.0.encode()?);
+
+encode!(Unix<OffsetDateTime>, me => Encoded::Borrowed(ValueRef::Int(me.0.unix_timestamp())));
+encode!(Unix<PrimitiveDateTime>, me => Unix(me.0.assume_utc()).encode()?);
+encode!(Unix<Date>, me => Unix(me.0.midnight()).encode()?);
+
+encode!(Julian<OffsetDateTime>, me => {
+    let day = me.0.unix_timestamp_nanos() as f64 / NANOS_PER_DAY + UNIX_EPOCH_JULIAN;
+    Encoded::Borrowed(ValueRef::Float(day))
+});
+encode!(Julian<PrimitiveDateTime>, me => Julian(me.0.assume_utc()).encode()?);
+encode!(Julian<Date>, me => Julian(me.0.midnight()).encode()?);
@@ -0,0 +1,75 @@
+use crate::{Decode, Encode, Encoded, Result, ValueRef, sqlite::DataType};
+
+/// An owned sqlite value.
+///
+/// Unlike [`ValueRef`], it does not borrow from the statement, so it can outlive the row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl Value {
+    /// Borrow current value as [`ValueRef`].
+    pub fn as_ref(&self) -> ValueRef<'_> {
+        match self {
+            Value::Null => ValueRef::Null,
+            Value::Int(int) => ValueRef::Int(*int),
+            Value::Float(fl) => ValueRef::Float(*fl),
+            Value::Text(t) => ValueRef::Text(t),
+            Value::Blob(b) => ValueRef::Blob(b),
+        }
+    }
+
+    pub fn data_type(&self) -> DataType {
+        self.as_ref().data_type()
+    }
+
+    /// Returns `true` if the value is `NULL`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn try_decode<'a, D: Decode<'a>>(&'a self) -> Result<D> {
+        D::decode(self.as_ref())
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(value: ValueRef<'_>) -> Self {
+        match value {
+            ValueRef::Null => Value::Null,
+            ValueRef::Int(int) => Value::Int(int),
+            ValueRef::Float(fl) => Value::Float(fl),
+            ValueRef::Text(t) => Value::Text(t.into()),
+            ValueRef::Blob(b) => Value::Blob(b.into()),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        value.as_ref()
+    }
+}
+
+impl Decode<'_> for Value {
+    fn decode(value: ValueRef) -> Result<Self> {
+        Ok(value.into())
+    }
+}
+
+impl<'a> Encode<'a> for Value {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Owned(self))
+    }
+}
+
+impl<'a> Encode<'a> for &'a Value {
+    fn encode(self) -> Result<Encoded<'a>> {
+        Ok(Encoded::Borrowed(self.as_ref()))
+    }
+}